@@ -0,0 +1,50 @@
+pub use stealth_address_kit::{curves, find_curve, DynStealthCurve, InvalidEncoding};
+
+pub struct Announcement {
+    pub stealth_address: Vec<u8>,
+    pub ephemeral_public_key: Vec<u8>,
+    pub view_tag: u64,
+}
+
+/// Generates a stealth address for the recipient with a fresh ephemeral key.
+pub fn send(
+    curve: &dyn DynStealthCurve,
+    spending_public_key: &[u8],
+    viewing_public_key: &[u8],
+) -> Result<Announcement, InvalidEncoding> {
+    let (ephemeral_private_key, ephemeral_public_key) = curve.random_keypair();
+    let (stealth_address, view_tag) = curve.generate_stealth_address(
+        viewing_public_key,
+        spending_public_key,
+        &ephemeral_private_key,
+    )?;
+    Ok(Announcement {
+        stealth_address,
+        ephemeral_public_key,
+        view_tag,
+    })
+}
+
+/// Returns the index and stealth private key of every announcement addressed to the keys,
+/// i.e. whose view tag matches and whose stealth address is the one the keys derive.
+pub fn scan(
+    curve: &dyn DynStealthCurve,
+    announcements: &[Announcement],
+    viewing_key: &[u8],
+    spending_key: &[u8],
+) -> Result<Vec<(usize, Vec<u8>)>, InvalidEncoding> {
+    curve.scan(
+        &announcements
+            .iter()
+            .map(|announcement| {
+                (
+                    announcement.stealth_address.clone(),
+                    announcement.ephemeral_public_key.clone(),
+                    announcement.view_tag,
+                )
+            })
+            .collect::<Vec<_>>(),
+        viewing_key,
+        spending_key,
+    )
+}
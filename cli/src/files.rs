@@ -0,0 +1,161 @@
+use crate::curves::Announcement;
+use crate::Error;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Bytes written as 0x-prefixed hex, in both JSON and CSV files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hex(pub Vec<u8>);
+
+impl Serialize for Hex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(&self.0)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Hex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        hex::decode(value.strip_prefix("0x").unwrap_or(&value))
+            .map(Hex)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The spending and viewing keys of a recipient, on one curve.
+#[derive(Serialize, Deserialize)]
+pub struct Keyfile {
+    pub curve: String,
+    pub spending_key: Hex,
+    pub spending_public_key: Hex,
+    pub viewing_key: Hex,
+    pub viewing_public_key: Hex,
+}
+
+impl Keyfile {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let file = File::open(path).map_err(Error::io(path))?;
+        serde_json::from_reader(file)
+            .map_err(|err| Error::new(format!("{}: {err}", path.display())))
+    }
+
+    /// Writes the keyfile, refusing to replace an existing one unless `overwrite` is set.
+    pub fn save(&self, path: &Path, overwrite: bool) -> Result<(), Error> {
+        let mut file = create_secret_file(path, overwrite)?;
+        serde_json::to_writer_pretty(&mut file, self).expect("keyfiles serialize");
+        writeln!(file).map_err(Error::io(path))
+    }
+}
+
+/// Creates a file for private keys, readable by its owner only.
+pub fn create_secret_file(path: &Path, overwrite: bool) -> Result<File, Error> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path).map_err(Error::io(path))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Picks the format from the file extension unless one is given, defaulting to JSON.
+    pub fn of(path: &Path, format: Option<Format>) -> Format {
+        format.unwrap_or_else(|| match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+            _ => Format::Json,
+        })
+    }
+}
+
+/// One announcement as stored in JSON and CSV files.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnnouncementRecord {
+    pub stealth_address: Hex,
+    pub ephemeral_public_key: Hex,
+    pub view_tag: u64,
+}
+
+impl From<&AnnouncementRecord> for Announcement {
+    fn from(record: &AnnouncementRecord) -> Self {
+        Announcement {
+            stealth_address: record.stealth_address.0.clone(),
+            ephemeral_public_key: record.ephemeral_public_key.0.clone(),
+            view_tag: record.view_tag,
+        }
+    }
+}
+
+impl From<Announcement> for AnnouncementRecord {
+    fn from(announcement: Announcement) -> Self {
+        AnnouncementRecord {
+            stealth_address: Hex(announcement.stealth_address),
+            ephemeral_public_key: Hex(announcement.ephemeral_public_key),
+            view_tag: announcement.view_tag,
+        }
+    }
+}
+
+/// Reads a JSON array, or a CSV file with a header row, of records.
+pub fn read_records<T: DeserializeOwned>(path: &Path, format: Format) -> Result<Vec<T>, Error> {
+    let file = File::open(path).map_err(Error::io(path))?;
+    let invalid = |err: &dyn std::fmt::Display| Error::new(format!("{}: {err}", path.display()));
+    match format {
+        Format::Json => serde_json::from_reader(file).map_err(|err| invalid(&err)),
+        Format::Csv => csv::Reader::from_reader(file)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(|err| invalid(&err)),
+    }
+}
+
+/// Writes records as a JSON array, or as CSV with a header row.
+pub fn write_records<T: Serialize, W: Write>(
+    writer: W,
+    records: &[T],
+    format: Format,
+) -> Result<(), Error> {
+    let failed = |err: &dyn std::fmt::Display| Error::new(format!("failed to write: {err}"));
+    match format {
+        Format::Json => {
+            let mut writer = writer;
+            serde_json::to_writer_pretty(&mut writer, records).map_err(|err| failed(&err))?;
+            writeln!(writer).map_err(|err| failed(&err))
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+            for record in records {
+                writer.serialize(record).map_err(|err| failed(&err))?;
+            }
+            writer.flush().map_err(|err| failed(&err))
+        }
+    }
+}
+
+/// Appends an announcement to a JSON or CSV file, creating it if it does not exist.
+pub fn append_announcement(
+    path: &Path,
+    format: Format,
+    announcement: AnnouncementRecord,
+) -> Result<(), Error> {
+    let mut records: Vec<AnnouncementRecord> = if path.exists() {
+        read_records(path, format)?
+    } else {
+        Vec::new()
+    };
+    records.push(announcement);
+
+    let file = File::create(path).map_err(Error::io(path))?;
+    write_records(file, &records, format)
+}
@@ -0,0 +1,273 @@
+//! A command-line tool to generate stealth address keys, publish meta-addresses,
+//! create announcements and scan them, on any compiled-in curve.
+mod curves;
+mod files;
+
+use clap::{Parser, Subcommand};
+use curves::{curves, find_curve, DynStealthCurve};
+use files::{AnnouncementRecord, Format, Hex, Keyfile};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// The chain prefix used in meta-addresses when none is given.
+const DEFAULT_CHAIN: &str = "eth";
+
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error(message.into())
+    }
+
+    pub fn io(path: &Path) -> impl FnOnce(std::io::Error) -> Self + '_ {
+        move |err| Error(format!("{}: {err}", path.display()))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "stealth-address", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the compiled-in curves
+    Curves,
+    /// Generate spending and viewing keys into a keyfile
+    Keygen {
+        /// The curve, as listed by `curves`
+        #[arg(long)]
+        curve: String,
+        #[arg(short, long, default_value = "stealth-keys.json")]
+        keyfile: PathBuf,
+        /// Replace an existing keyfile
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the meta-address of a keyfile
+    MetaAddress {
+        #[arg(short, long, default_value = "stealth-keys.json")]
+        keyfile: PathBuf,
+        #[arg(long, default_value = DEFAULT_CHAIN)]
+        chain: String,
+    },
+    /// Create an announcement, paying to a fresh stealth address of a meta-address
+    Announce {
+        /// The curve of the meta-address
+        #[arg(long)]
+        curve: String,
+        /// An `st:<chain>:0x<spending public key><viewing public key>` meta-address
+        meta_address: String,
+        /// Append the announcement to this JSON or CSV file, instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// The output file format; guessed from its extension by default
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// List the announcements of a JSON or CSV file that pay to a keyfile
+    Scan {
+        #[arg(short, long, default_value = "stealth-keys.json")]
+        keyfile: PathBuf,
+        announcements: PathBuf,
+        /// The announcements file format; guessed from its extension by default
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Export the stealth private keys of the announcements that pay to a keyfile
+    Export {
+        #[arg(short, long, default_value = "stealth-keys.json")]
+        keyfile: PathBuf,
+        announcements: PathBuf,
+        /// The announcements file format; guessed from its extension by default
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Write the keys to this JSON or CSV file, instead of printing them
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// The output format; guessed from the output file extension by default
+        #[arg(long, value_enum)]
+        output_format: Option<Format>,
+        /// Replace an existing output file
+        #[arg(long, requires = "output")]
+        force: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct ScanRecord {
+    index: usize,
+    stealth_address: Hex,
+    ephemeral_public_key: Hex,
+    view_tag: u64,
+}
+
+#[derive(Serialize)]
+struct ExportRecord {
+    index: usize,
+    stealth_address: Hex,
+    stealth_private_key: Hex,
+}
+
+fn curve(name: &str) -> Result<&'static dyn DynStealthCurve, Error> {
+    find_curve(name).ok_or_else(|| {
+        let names: Vec<_> = curves().iter().map(|curve| curve.name()).collect();
+        Error::new(format!(
+            "curve `{name}` is not compiled in, expected one of: {}",
+            names.join(", ")
+        ))
+    })
+}
+
+/// Loads a keyfile and the announcements, returning the matching announcements along with
+/// their stealth private keys.
+fn scan(
+    keyfile: &Path,
+    announcements: &Path,
+    format: Option<Format>,
+) -> Result<Vec<(usize, AnnouncementRecord, Vec<u8>)>, Error> {
+    let keys = Keyfile::load(keyfile)?;
+    let ops = curve(&keys.curve)?;
+    let records: Vec<AnnouncementRecord> =
+        files::read_records(announcements, Format::of(announcements, format))?;
+    let found = curves::scan(
+        ops,
+        &records.iter().map(Into::into).collect::<Vec<_>>(),
+        &keys.viewing_key.0,
+        &keys.spending_key.0,
+    )
+    .map_err(|_| {
+        Error::new("the keyfile or announcements are not valid encodings for the curve")
+    })?;
+
+    Ok(found
+        .into_iter()
+        .map(|(index, stealth_private_key)| (index, records[index].clone(), stealth_private_key))
+        .collect())
+}
+
+fn run(command: Command) -> Result<(), Error> {
+    match command {
+        Command::Curves => {
+            for curve in curves() {
+                println!("{}", curve.name());
+            }
+        }
+        Command::Keygen {
+            curve: name,
+            keyfile,
+            force,
+        } => {
+            let ops = curve(&name)?;
+            let (spending_key, spending_public_key) = ops.random_keypair();
+            let (viewing_key, viewing_public_key) = ops.random_keypair();
+            Keyfile {
+                curve: name,
+                spending_key: Hex(spending_key),
+                spending_public_key: Hex(spending_public_key),
+                viewing_key: Hex(viewing_key),
+                viewing_public_key: Hex(viewing_public_key),
+            }
+            .save(&keyfile, force)?;
+            eprintln!("wrote {}", keyfile.display());
+        }
+        Command::MetaAddress { keyfile, chain } => {
+            let keys = Keyfile::load(&keyfile)?;
+            println!(
+                "st:{chain}:0x{}{}",
+                hex::encode(&keys.spending_public_key.0),
+                hex::encode(&keys.viewing_public_key.0)
+            );
+        }
+        Command::Announce {
+            curve: name,
+            meta_address,
+            output,
+            format,
+        } => {
+            let ops = curve(&name)?;
+            let (_, spending_public_key, viewing_public_key) = ops
+                .parse_meta_address(&meta_address)
+                .map_err(|err| Error::new(err.to_string()))?;
+            let announcement: AnnouncementRecord =
+                curves::send(ops, &spending_public_key, &viewing_public_key)
+                    .map_err(|_| Error::new("the meta-address keys are not points on the curve"))?
+                    .into();
+            match output {
+                Some(path) => {
+                    files::append_announcement(&path, Format::of(&path, format), announcement)?
+                }
+                None => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&announcement).expect("announcements serialize")
+                ),
+            }
+        }
+        Command::Scan {
+            keyfile,
+            announcements,
+            format,
+        } => {
+            let found: Vec<_> = scan(&keyfile, &announcements, format)?
+                .into_iter()
+                .map(|(index, announcement, _)| ScanRecord {
+                    index,
+                    stealth_address: announcement.stealth_address,
+                    ephemeral_public_key: announcement.ephemeral_public_key,
+                    view_tag: announcement.view_tag,
+                })
+                .collect();
+            files::write_records(std::io::stdout().lock(), &found, Format::Json)?;
+        }
+        Command::Export {
+            keyfile,
+            announcements,
+            format,
+            output,
+            output_format,
+            force,
+        } => {
+            let keys: Vec<_> = scan(&keyfile, &announcements, format)?
+                .into_iter()
+                .map(|(index, announcement, stealth_private_key)| ExportRecord {
+                    index,
+                    stealth_address: announcement.stealth_address,
+                    stealth_private_key: Hex(stealth_private_key),
+                })
+                .collect();
+            match output {
+                Some(path) => {
+                    let file = files::create_secret_file(&path, force)?;
+                    files::write_records(file, &keys, Format::of(&path, output_format))?;
+                }
+                None => files::write_records(
+                    std::io::stdout().lock(),
+                    &keys,
+                    output_format.unwrap_or(Format::Json),
+                )?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
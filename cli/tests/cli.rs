@@ -0,0 +1,388 @@
+//! Integration tests, running the stealth-address binary on temporary files.
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const BIN: &str = env!("CARGO_BIN_EXE_stealth-address");
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(BIN)
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the command, asserting that it succeeds, and returns its stdout.
+fn ok(dir: &Path, args: &[&str]) -> String {
+    let output = run(dir, args);
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs the command, asserting that it fails, and returns its stderr.
+fn fails(dir: &Path, args: &[&str]) -> String {
+    let output = run(dir, args);
+    assert!(!output.status.success(), "{args:?} succeeded");
+    String::from_utf8(output.stderr).unwrap()
+}
+
+fn curves() -> Vec<String> {
+    ok(Path::new("."), &["curves"])
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+struct Recipient {
+    keyfile: PathBuf,
+    meta_address: String,
+}
+
+fn recipient(dir: &TempDir, curve: &str, name: &str) -> Recipient {
+    let keyfile = dir.path().join(format!("{name}.json"));
+    ok(
+        dir.path(),
+        &["keygen", "--curve", curve, "-k", keyfile.to_str().unwrap()],
+    );
+    let meta_address = ok(
+        dir.path(),
+        &["meta-address", "-k", keyfile.to_str().unwrap()],
+    )
+    .trim()
+    .to_owned();
+    Recipient {
+        keyfile,
+        meta_address,
+    }
+}
+
+fn announce(dir: &TempDir, curve: &str, meta_address: &str, output: &str) {
+    ok(
+        dir.path(),
+        &["announce", "--curve", curve, meta_address, "-o", output],
+    );
+}
+
+#[test]
+fn curves_lists_compiled_in_curves() {
+    let curves = curves();
+    assert!(!curves.is_empty());
+    #[cfg(feature = "secp256k1")]
+    assert!(curves.contains(&"secp256k1".to_owned()));
+}
+
+#[test]
+fn keygen_writes_a_keyfile() {
+    let dir = tempfile::tempdir().unwrap();
+    let curve = &curves()[0];
+    ok(dir.path(), &["keygen", "--curve", curve]);
+
+    let keyfile: Value = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("stealth-keys.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(keyfile["curve"], curve.as_str());
+    for field in [
+        "spending_key",
+        "spending_public_key",
+        "viewing_key",
+        "viewing_public_key",
+    ] {
+        assert!(keyfile[field].as_str().unwrap().starts_with("0x"));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(dir.path().join("stealth-keys.json")).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    // an existing keyfile is only replaced with --force
+    assert!(fails(dir.path(), &["keygen", "--curve", curve]).contains("stealth-keys.json"));
+    ok(dir.path(), &["keygen", "--curve", curve, "--force"]);
+    let replaced: Value = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("stealth-keys.json")).unwrap(),
+    )
+    .unwrap();
+    assert_ne!(replaced["spending_key"], keyfile["spending_key"]);
+
+    assert!(fails(
+        dir.path(),
+        &["keygen", "--curve", "ed448", "-k", "other.json"]
+    )
+    .contains("not compiled in"));
+}
+
+#[test]
+fn meta_address_concatenates_the_public_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let recipient = recipient(&dir, &curves()[0], "alice");
+    let keyfile: Value =
+        serde_json::from_str(&std::fs::read_to_string(&recipient.keyfile).unwrap()).unwrap();
+
+    let expected = format!(
+        "{}{}",
+        keyfile["spending_public_key"].as_str().unwrap(),
+        keyfile["viewing_public_key"]
+            .as_str()
+            .unwrap()
+            .trim_start_matches("0x")
+    );
+    assert_eq!(recipient.meta_address, format!("st:eth:{expected}"));
+
+    let meta_address = ok(
+        dir.path(),
+        &[
+            "meta-address",
+            "-k",
+            recipient.keyfile.to_str().unwrap(),
+            "--chain",
+            "base",
+        ],
+    );
+    assert_eq!(meta_address.trim(), format!("st:base:{expected}"));
+
+    assert!(!fails(dir.path(), &["meta-address", "-k", "missing.json"]).is_empty());
+}
+
+#[test]
+fn announce_prints_or_appends_announcements() {
+    let dir = tempfile::tempdir().unwrap();
+    let curve = &curves()[0];
+    let recipient = recipient(&dir, curve, "alice");
+
+    let announcement: Value = serde_json::from_str(&ok(
+        dir.path(),
+        &["announce", "--curve", curve, &recipient.meta_address],
+    ))
+    .unwrap();
+    assert!(announcement["stealth_address"]
+        .as_str()
+        .unwrap()
+        .starts_with("0x"));
+    assert!(announcement["ephemeral_public_key"]
+        .as_str()
+        .unwrap()
+        .starts_with("0x"));
+    assert!(announcement["view_tag"].is_u64());
+
+    announce(&dir, curve, &recipient.meta_address, "announcements.json");
+    announce(&dir, curve, &recipient.meta_address, "announcements.json");
+    let announcements: Value = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("announcements.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(announcements.as_array().unwrap().len(), 2);
+
+    announce(&dir, curve, &recipient.meta_address, "announcements.csv");
+    announce(&dir, curve, &recipient.meta_address, "announcements.csv");
+    let csv = std::fs::read_to_string(dir.path().join("announcements.csv")).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("stealth_address,ephemeral_public_key,view_tag")
+    );
+    assert_eq!(lines.count(), 2);
+
+    assert!(
+        fails(dir.path(), &["announce", "--curve", curve, "st:eth:0x00"]).contains("meta-address")
+    );
+    assert!(fails(dir.path(), &["announce", "--curve", curve, "alice"]).contains("meta-address"));
+    // keys of the right length that are not points of the curve
+    let keys = recipient.meta_address.rsplit_once("0x").unwrap().1;
+    let off_curve = format!("st:eth:0x{}", "f".repeat(keys.len()));
+    assert!(
+        fails(dir.path(), &["announce", "--curve", curve, &off_curve])
+            .contains("not two public keys on the curve")
+    );
+}
+
+#[test]
+fn scan_finds_only_the_recipients_announcements() {
+    let dir = tempfile::tempdir().unwrap();
+    let curve = &curves()[0];
+    let alice = recipient(&dir, curve, "alice");
+    let bob = recipient(&dir, curve, "bob");
+
+    for file in ["announcements.json", "announcements.csv"] {
+        announce(&dir, curve, &bob.meta_address, file);
+        announce(&dir, curve, &alice.meta_address, file);
+        announce(&dir, curve, &bob.meta_address, file);
+        announce(&dir, curve, &alice.meta_address, file);
+
+        let found: Value = serde_json::from_str(&ok(
+            dir.path(),
+            &["scan", "-k", alice.keyfile.to_str().unwrap(), file],
+        ))
+        .unwrap();
+        let indices: Vec<_> = found
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|found| found["index"].as_u64().unwrap())
+            .collect();
+        assert_eq!(indices, [1, 3]);
+        assert!(found[0].get("stealth_private_key").is_none());
+    }
+
+    // the format can be given explicitly
+    std::fs::copy(
+        dir.path().join("announcements.csv"),
+        dir.path().join("announcements.txt"),
+    )
+    .unwrap();
+    let found: Value = serde_json::from_str(&ok(
+        dir.path(),
+        &[
+            "scan",
+            "-k",
+            bob.keyfile.to_str().unwrap(),
+            "announcements.txt",
+            "--format",
+            "csv",
+        ],
+    ))
+    .unwrap();
+    assert_eq!(found.as_array().unwrap().len(), 2);
+
+    std::fs::write(dir.path().join("broken.json"), "[{}]").unwrap();
+    assert!(fails(
+        dir.path(),
+        &["scan", "-k", alice.keyfile.to_str().unwrap(), "broken.json"]
+    )
+    .contains("broken.json"));
+}
+
+#[test]
+fn export_writes_the_stealth_private_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let curve = &curves()[0];
+    let alice = recipient(&dir, curve, "alice");
+    let bob = recipient(&dir, curve, "bob");
+    announce(&dir, curve, &alice.meta_address, "announcements.json");
+    announce(&dir, curve, &bob.meta_address, "announcements.json");
+
+    let printed: Value = serde_json::from_str(&ok(
+        dir.path(),
+        &[
+            "export",
+            "-k",
+            alice.keyfile.to_str().unwrap(),
+            "announcements.json",
+        ],
+    ))
+    .unwrap();
+    let announcements: Value = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("announcements.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(printed.as_array().unwrap().len(), 1);
+    assert_eq!(printed[0]["index"], 0);
+    assert_eq!(
+        printed[0]["stealth_address"],
+        announcements[0]["stealth_address"]
+    );
+
+    ok(
+        dir.path(),
+        &[
+            "export",
+            "-k",
+            alice.keyfile.to_str().unwrap(),
+            "announcements.json",
+            "-o",
+            "keys.csv",
+        ],
+    );
+    let csv = std::fs::read_to_string(dir.path().join("keys.csv")).unwrap();
+    let stealth_private_key = printed[0]["stealth_private_key"].as_str().unwrap();
+    assert_eq!(
+        csv.lines().collect::<Vec<_>>(),
+        [
+            "index,stealth_address,stealth_private_key".to_owned(),
+            format!(
+                "0,{},{stealth_private_key}",
+                announcements[0]["stealth_address"].as_str().unwrap()
+            )
+        ]
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(dir.path().join("keys.csv")).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    // an existing output file is only replaced with --force
+    let export = [
+        "export",
+        "-k",
+        alice.keyfile.to_str().unwrap(),
+        "announcements.json",
+        "-o",
+        "keys.csv",
+    ];
+    assert!(fails(dir.path(), &export).contains("keys.csv"));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("keys.csv")).unwrap(),
+        csv
+    );
+    ok(dir.path(), &[&export[..], &["--force"]].concat());
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("keys.csv")).unwrap(),
+        csv
+    );
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn exported_keys_control_the_stealth_addresses() {
+    use ark_serialize::CanonicalDeserialize;
+    use stealth_address_kit::{Secp256k1, StealthAddressOnCurve};
+
+    let dir = tempfile::tempdir().unwrap();
+    let alice = recipient(&dir, "secp256k1", "alice");
+    announce(&dir, "secp256k1", &alice.meta_address, "announcements.csv");
+    let exported: Value = serde_json::from_str(&ok(
+        dir.path(),
+        &[
+            "export",
+            "-k",
+            alice.keyfile.to_str().unwrap(),
+            "announcements.csv",
+        ],
+    ))
+    .unwrap();
+
+    let decode = |value: &Value| hex::decode(&value.as_str().unwrap()[2..]).unwrap();
+    let stealth_private_key =
+        ark_secp256k1::Fr::deserialize_compressed(&decode(&exported[0]["stealth_private_key"])[..])
+            .unwrap();
+    // stealth addresses are exported in SEC1 format
+    let stealth_address =
+        Secp256k1::encode_point(&Secp256k1::derive_public_key(&stealth_private_key));
+    assert_eq!(stealth_address, decode(&exported[0]["stealth_address"]));
+}
+
+#[test]
+fn round_trip_on_every_curve() {
+    let dir = tempfile::tempdir().unwrap();
+    for curve in curves() {
+        let alice = recipient(&dir, &curve, &format!("alice-{curve}"));
+        let file = format!("{curve}.csv");
+        announce(&dir, &curve, &alice.meta_address, &file);
+
+        let exported: Value = serde_json::from_str(&ok(
+            dir.path(),
+            &["export", "-k", alice.keyfile.to_str().unwrap(), &file],
+        ))
+        .unwrap();
+        assert_eq!(exported.as_array().unwrap().len(), 1, "{curve}");
+    }
+}
@@ -0,0 +1,8 @@
+use pyo3::prelude::*;
+
+/// Python bindings for every curve enabled through this crate's features.
+#[pymodule]
+#[pyo3(name = "stealth_address_kit")]
+fn stealth_address_kit_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    stealth_address_kit::register_python_module(m)
+}
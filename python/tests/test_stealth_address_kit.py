@@ -0,0 +1,134 @@
+import pytest
+
+import stealth_address_kit
+
+CURVE_NAMES = [
+    "BabyJubJub",
+    "Bandersnatch",
+    "Bls12_377",
+    "Bls12_377G2",
+    "Bls12_381",
+    "Bls12_381G2",
+    "Bn254",
+    "Bn254G2",
+    "BW6_761",
+    "BW6_761G2",
+    "Ed25519",
+    "EdOnBls12_377",
+    "Grumpkin",
+    "Jubjub",
+    "Pallas",
+    "Ristretto255",
+    "Secp256k1",
+    "Secp256r1",
+    "Secq256k1",
+    "StarkCurve",
+    "Vesta",
+]
+CURVES = [
+    getattr(stealth_address_kit, name)
+    for name in CURVE_NAMES
+    if hasattr(stealth_address_kit, name)
+]
+
+
+@pytest.fixture(params=CURVES, ids=lambda curve: curve.__name__)
+def curve(request):
+    return request.param
+
+
+def test_random_keypair(curve):
+    private_key, public_key = curve.random_keypair()
+    assert len(private_key) == curve.SCALAR_SIZE
+    assert len(public_key) == curve.POINT_SIZE
+    assert curve.derive_public_key(private_key) == public_key
+
+
+def test_curve_names_are_unique():
+    names = [curve.NAME for curve in CURVES]
+    assert len(set(names)) == len(names)
+
+
+def test_generate_random_fr(curve):
+    assert curve.generate_random_fr() != curve.generate_random_fr()
+
+
+def test_stealth_address_round_trip(curve):
+    spending_key, spending_public_key = curve.random_keypair()
+    viewing_key, viewing_public_key = curve.random_keypair()
+    ephemeral_private_key, ephemeral_public_key = curve.random_keypair()
+
+    stealth_address, view_tag = curve.generate_stealth_address(
+        viewing_public_key, spending_public_key, ephemeral_private_key
+    )
+    stealth_private_key = curve.generate_stealth_private_key(
+        ephemeral_public_key, viewing_key, spending_key, view_tag
+    )
+
+    assert stealth_private_key is not None
+    assert curve.derive_public_key(stealth_private_key) == stealth_address
+
+
+def test_mismatched_view_tag(curve):
+    spending_key, spending_public_key = curve.random_keypair()
+    viewing_key, viewing_public_key = curve.random_keypair()
+    ephemeral_private_key, ephemeral_public_key = curve.random_keypair()
+
+    _, view_tag = curve.generate_stealth_address(
+        viewing_public_key, spending_public_key, ephemeral_private_key
+    )
+    mismatched_view_tag = (view_tag + 1) % 2**64
+
+    assert (
+        curve.generate_stealth_private_key(
+            ephemeral_public_key, viewing_key, spending_key, mismatched_view_tag
+        )
+        is None
+    )
+
+
+def test_scan(curve):
+    spending_key, spending_public_key = curve.random_keypair()
+    viewing_key, viewing_public_key = curve.random_keypair()
+    _, other_viewing_public_key = curve.random_keypair()
+
+    announcements = []
+    stealth_addresses = []
+    for recipient_viewing_public_key in [
+        other_viewing_public_key,
+        viewing_public_key,
+        other_viewing_public_key,
+    ]:
+        ephemeral_private_key, ephemeral_public_key = curve.random_keypair()
+        stealth_address, view_tag = curve.generate_stealth_address(
+            recipient_viewing_public_key, spending_public_key, ephemeral_private_key
+        )
+        announcements.append((stealth_address, ephemeral_public_key, view_tag))
+        stealth_addresses.append(stealth_address)
+    # a view tag that matches does not make up for a stealth address that does not
+    _, ephemeral_public_key, view_tag = announcements[1]
+    announcements.append((spending_public_key, ephemeral_public_key, view_tag))
+
+    found = curve.scan(announcements, viewing_key, spending_key)
+
+    assert [index for index, _ in found] == [1]
+    assert curve.derive_public_key(found[0][1]) == stealth_addresses[1]
+
+
+def test_rejects_malformed_bytes(curve):
+    private_key, public_key = curve.random_keypair()
+
+    with pytest.raises(stealth_address_kit.InvalidEncodingError):
+        curve.derive_public_key(private_key[:-1])
+    with pytest.raises(stealth_address_kit.InvalidEncodingError):
+        curve.derive_public_key(private_key + b"\x00")
+    with pytest.raises(stealth_address_kit.InvalidEncodingError):
+        curve.derive_public_key(b"\xff" * curve.SCALAR_SIZE)
+    with pytest.raises(stealth_address_kit.InvalidEncodingError):
+        curve.generate_stealth_address(
+            b"\xff" * curve.POINT_SIZE, public_key, private_key
+        )
+
+
+def test_invalid_encoding_error_is_value_error():
+    assert issubclass(stealth_address_kit.InvalidEncodingError, ValueError)
@@ -16,19 +16,34 @@ fn criterion_benchmark(c: &mut Criterion) {
     }
     #[cfg(feature = "bn254")]
     {
-        use ark_bn254::Bn254;
+        use stealth_address_kit::Bn254;
         define_curve_benchmarks!(Bn254, c);
     }
+    #[cfg(feature = "bn254")]
+    {
+        use stealth_address_kit::Bn254G2;
+        define_curve_benchmarks!(Bn254G2, c);
+    }
     #[cfg(feature = "bls12_381")]
     {
-        use ark_bls12_381::Bls12_381;
+        use stealth_address_kit::Bls12_381;
         define_curve_benchmarks!(Bls12_381, c);
     }
+    #[cfg(feature = "bls12_381")]
+    {
+        use stealth_address_kit::Bls12_381G2;
+        define_curve_benchmarks!(Bls12_381G2, c);
+    }
     #[cfg(feature = "bls12_377")]
     {
-        use ark_bls12_377::Bls12_377;
+        use stealth_address_kit::Bls12_377;
         define_curve_benchmarks!(Bls12_377, c);
     }
+    #[cfg(feature = "bls12_377")]
+    {
+        use stealth_address_kit::Bls12_377G2;
+        define_curve_benchmarks!(Bls12_377G2, c);
+    }
     #[cfg(feature = "pallas")]
     {
         use stealth_address_kit::Pallas;
@@ -44,11 +59,56 @@ fn criterion_benchmark(c: &mut Criterion) {
         use stealth_address_kit::BabyJubJub;
         define_curve_benchmarks!(BabyJubJub, c);
     }
+    #[cfg(feature = "ed25519")]
+    {
+        use stealth_address_kit::Ed25519;
+        define_curve_benchmarks!(Ed25519, c);
+    }
+    #[cfg(feature = "ristretto255")]
+    {
+        use stealth_address_kit::Ristretto255;
+        define_curve_benchmarks!(Ristretto255, c);
+    }
+    #[cfg(feature = "jubjub")]
+    {
+        use stealth_address_kit::Jubjub;
+        define_curve_benchmarks!(Jubjub, c);
+    }
+    #[cfg(feature = "bandersnatch")]
+    {
+        use stealth_address_kit::Bandersnatch;
+        define_curve_benchmarks!(Bandersnatch, c);
+    }
+    #[cfg(feature = "ed_on_bls12_377")]
+    {
+        use stealth_address_kit::EdOnBls12_377;
+        define_curve_benchmarks!(EdOnBls12_377, c);
+    }
+    #[cfg(feature = "grumpkin")]
+    {
+        use stealth_address_kit::Grumpkin;
+        define_curve_benchmarks!(Grumpkin, c);
+    }
+    #[cfg(feature = "secq256k1")]
+    {
+        use stealth_address_kit::Secq256k1;
+        define_curve_benchmarks!(Secq256k1, c);
+    }
+    #[cfg(feature = "stark_curve")]
+    {
+        use stealth_address_kit::StarkCurve;
+        define_curve_benchmarks!(StarkCurve, c);
+    }
     #[cfg(feature = "bw6_761")]
     {
-        use ark_bw6_761::BW6_761;
+        use stealth_address_kit::BW6_761;
         define_curve_benchmarks!(BW6_761, c);
     }
+    #[cfg(feature = "bw6_761")]
+    {
+        use stealth_address_kit::BW6_761G2;
+        define_curve_benchmarks!(BW6_761G2, c);
+    }
 }
 
 #[macro_export]
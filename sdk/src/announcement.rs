@@ -0,0 +1,89 @@
+use crate::prelude::Fr;
+use crate::stealth_addresses::{HasViewTag, StealthAddressOnCurve};
+use std::fmt::{Debug, Formatter};
+
+/// What a sender publishes for a payment, for the recipient to find it by.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Announcement<C: StealthAddressOnCurve> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::compressed::serialize_point::<C, _>",
+            deserialize_with = "crate::compressed::deserialize_point::<C, _>"
+        )
+    )]
+    pub stealth_address: C::Projective,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::compressed::serialize_point::<C, _>",
+            deserialize_with = "crate::compressed::deserialize_point::<C, _>"
+        )
+    )]
+    pub ephemeral_public_key: C::Projective,
+    pub view_tag: u64,
+}
+
+impl<C: StealthAddressOnCurve> Announcement<C> {
+    pub fn new(
+        stealth_address: C::Projective,
+        ephemeral_public_key: C::Projective,
+        view_tag: u64,
+    ) -> Self {
+        Announcement {
+            stealth_address,
+            ephemeral_public_key,
+            view_tag,
+        }
+    }
+
+    /// Returns the stealth private key, if the announcement is addressed to the keys, i.e. its
+    /// view tag matches and its stealth address is the one the keys derive.
+    pub fn stealth_private_key(&self, viewing_key: Fr<C>, spending_key: Fr<C>) -> Option<Fr<C>>
+    where
+        Fr<C>: HasViewTag,
+    {
+        C::generate_stealth_private_key(
+            self.ephemeral_public_key,
+            viewing_key,
+            spending_key,
+            self.view_tag,
+        )
+        .filter(|stealth_private_key| {
+            C::derive_public_key(stealth_private_key) == self.stealth_address
+        })
+    }
+}
+
+// implemented by hand, as deriving would require the curve marker to implement them too
+impl<C: StealthAddressOnCurve> Clone for Announcement<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: StealthAddressOnCurve> Copy for Announcement<C> {}
+
+impl<C: StealthAddressOnCurve> PartialEq for Announcement<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.stealth_address == other.stealth_address
+            && self.ephemeral_public_key == other.ephemeral_public_key
+            && self.view_tag == other.view_tag
+    }
+}
+
+impl<C: StealthAddressOnCurve> Eq for Announcement<C> {}
+
+impl<C: StealthAddressOnCurve> Debug for Announcement<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Announcement")
+            .field("stealth_address", &self.stealth_address)
+            .field("ephemeral_public_key", &self.ephemeral_public_key)
+            .field("view_tag", &self.view_tag)
+            .finish()
+    }
+}
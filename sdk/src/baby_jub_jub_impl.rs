@@ -6,10 +6,21 @@ pub struct BabyJubJub;
 
 impl StealthAddressOnCurve for BabyJubJub {
     type Projective = EdwardsProjective;
+    const NAME: &'static str = "baby_jub_jub";
+    const COMPRESSED_POINT_SIZE: usize = 32;
+    const UNCOMPRESSED_POINT_SIZE: usize = 64;
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(baby_jub_jub, BabyJubJub, Fr, EdwardsProjective, 32, 32);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(BabyJubJub, Fr, EdwardsProjective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(BabyJubJub, Fr, EdwardsProjective);
 define_curve_tests!(BabyJubJub);
@@ -0,0 +1,54 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the Bandersnatch curve, embedded in BLS12-381.
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ed_on_bls12_381_bandersnatch::{EdwardsProjective, Fr};
+
+pub struct Bandersnatch;
+
+impl StealthAddressOnCurve for Bandersnatch {
+    type Projective = EdwardsProjective;
+    const NAME: &'static str = "bandersnatch";
+    const COMPRESSED_POINT_SIZE: usize = 32;
+    const UNCOMPRESSED_POINT_SIZE: usize = 64;
+
+    /// The curve has a cofactor of 4, which is cleared from the shared point.
+    #[inline]
+    fn compute_shared_point(private_key: Fr, public_key: EdwardsProjective) -> EdwardsProjective {
+        (public_key * private_key)
+            .into_affine()
+            .mul_by_cofactor_to_group()
+    }
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(bandersnatch, Bandersnatch, Fr, EdwardsProjective, 32, 32);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bandersnatch, Fr, EdwardsProjective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bandersnatch, Fr, EdwardsProjective);
+define_curve_tests!(Bandersnatch);
+
+#[cfg(test)]
+mod cofactor_tests {
+    use super::*;
+    use ark_ed_on_bls12_381_bandersnatch::{EdwardsAffine, Fq};
+    use ark_ff::{One, Zero};
+
+    #[test]
+    fn shared_point_ignores_small_order_components() {
+        let (private_key, public_key) = Bandersnatch::random_keypair();
+        // (0, -1) has order 2 on every twisted Edwards curve
+        let torsion = EdwardsAffine::new_unchecked(Fq::zero(), -Fq::one());
+
+        assert_eq!(
+            Bandersnatch::compute_shared_point(private_key, public_key + torsion),
+            Bandersnatch::compute_shared_point(private_key, public_key)
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the G2 group of the Bls12_377 curve.
+use ark_bls12_377::{Fr, G2Projective};
+
+/// Stealth keys in the G2 group of Bls12_377, as the G1 keys of `Bls12_377` are to G1.
+pub struct Bls12_377G2;
+
+impl StealthAddressOnCurve for Bls12_377G2 {
+    type Projective = G2Projective;
+    const NAME: &'static str = "bls12_377_g2";
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(bls12_377_g2, Bls12_377G2, Fr, G2Projective, 32, 96);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bls12_377G2, Fr, G2Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bls12_377G2, Fr, G2Projective);
+define_curve_tests!(Bls12_377G2);
@@ -1,13 +1,25 @@
 use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
 /// Implementation of the StealthAddressOnCurve trait for the Bls12_377 curve.
-use ark_bls12_377::{Bls12_377, Fr, G1Projective};
+use ark_bls12_377::{Fr, G1Projective};
+
+/// Stealth keys in the G1 group of Bls12_377.
+pub struct Bls12_377;
 
 impl StealthAddressOnCurve for Bls12_377 {
     type Projective = G1Projective;
+    const NAME: &'static str = "bls12_377";
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(bls12_377, Bls12_377, Fr, G1Projective, 32, 48);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bls12_377, Fr, G1Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bls12_377, Fr, G1Projective);
 define_curve_tests!(Bls12_377);
@@ -0,0 +1,47 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the G2 group of the Bls12_381 curve.
+use ark_bls12_381::{Fr, G2Projective};
+
+/// Stealth keys in the G2 group of Bls12_381, as the G1 keys of `Bls12_381` are to G1.
+pub struct Bls12_381G2;
+
+impl StealthAddressOnCurve for Bls12_381G2 {
+    type Projective = G2Projective;
+    const NAME: &'static str = "bls12_381_g2";
+    // as for G1, the default encodings are the Zcash ones, with the coefficients of the
+    // coordinates in descending order
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(bls12_381_g2, Bls12_381G2, Fr, G2Projective, 32, 96);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bls12_381G2, Fr, G2Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bls12_381G2, Fr, G2Projective);
+define_curve_tests!(Bls12_381G2);
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use ark_ec::Group;
+
+    #[test]
+    fn encodes_points_like_zcash() {
+        let generator = G2Projective::generator();
+        let compressed = hex::decode(concat!(
+            "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049",
+            "334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051",
+            "c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        ))
+        .unwrap();
+
+        assert_eq!(Bls12_381G2::encode_point(&generator), compressed);
+        assert_eq!(Bls12_381G2::decode_point(&compressed), Ok(generator));
+    }
+}
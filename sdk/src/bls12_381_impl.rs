@@ -1,13 +1,87 @@
 use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
 /// Implementation of the StealthAddressOnCurve trait for the Bls12_381 curve.
-use ark_bls12_381::{Bls12_381, Fr, G1Projective};
+use ark_bls12_381::{Fr, G1Projective};
+
+/// Stealth keys in the G1 group of Bls12_381.
+pub struct Bls12_381;
 
 impl StealthAddressOnCurve for Bls12_381 {
     type Projective = G1Projective;
+    const NAME: &'static str = "bls12_381";
+    // arkworks encodes BLS12-381 points in the Zcash format, big-endian with the compression,
+    // infinity and sign flags in the top bits, so the default encodings are the standard ones
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(bls12_381, Bls12_381, Fr, G1Projective, 32, 48);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bls12_381, Fr, G1Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bls12_381, Fr, G1Projective);
 define_curve_tests!(Bls12_381);
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use ark_ec::Group;
+    use ark_ff::Zero;
+
+    const GENERATOR_X: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const GENERATOR_Y: &str = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+    #[test]
+    fn encodes_points_like_zcash() {
+        let generator = G1Projective::generator();
+        // the compression flag is set, and the sign flag is not
+        let mut compressed = hex::decode(GENERATOR_X).unwrap();
+        compressed[0] |= 0x80;
+        let uncompressed = hex::decode(format!("{GENERATOR_X}{GENERATOR_Y}")).unwrap();
+
+        assert_eq!(Bls12_381::encode_point(&generator), compressed);
+        assert_eq!(Bls12_381::decode_point(&compressed), Ok(generator));
+        assert_eq!(
+            Bls12_381::encode_point_uncompressed(&generator),
+            uncompressed
+        );
+        assert_eq!(
+            Bls12_381::decode_point_uncompressed(&uncompressed),
+            Ok(generator)
+        );
+
+        // the sign flag is set for the larger y
+        let mut negated = compressed.clone();
+        negated[0] |= 0x20;
+        assert_eq!(Bls12_381::encode_point(&-generator), negated);
+
+        let mut infinity = vec![0u8; Bls12_381::COMPRESSED_POINT_SIZE];
+        infinity[0] = 0xc0;
+        assert_eq!(Bls12_381::encode_point(&G1Projective::zero()), infinity);
+    }
+
+    #[test]
+    fn rejects_bad_point_encodings() {
+        let compressed = Bls12_381::encode_point(&G1Projective::generator());
+        let uncompressed = Bls12_381::encode_point_uncompressed(&G1Projective::generator());
+
+        // the compression flag must match the format
+        let mut unflagged = compressed.clone();
+        unflagged[0] &= 0x7f;
+        assert!(Bls12_381::decode_point(&unflagged).is_err());
+        let mut flagged = uncompressed.clone();
+        flagged[0] |= 0x80;
+        assert!(Bls12_381::decode_point_uncompressed(&flagged).is_err());
+
+        // the point at infinity with a sign flag
+        let mut infinity = vec![0u8; Bls12_381::COMPRESSED_POINT_SIZE];
+        infinity[0] = 0xe0;
+        assert!(Bls12_381::decode_point(&infinity).is_err());
+
+        assert!(Bls12_381::decode_point(&compressed[1..]).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the G2 group of the Bn254 curve.
+use ark_bn254::{Fr, G2Projective};
+
+/// Stealth keys in the G2 group of Bn254, as the G1 keys of `Bn254` are to G1.
+pub struct Bn254G2;
+
+impl StealthAddressOnCurve for Bn254G2 {
+    type Projective = G2Projective;
+    const NAME: &'static str = "bn254_g2";
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(bn254_g2, Bn254G2, Fr, G2Projective, 32, 64);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bn254G2, Fr, G2Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bn254G2, Fr, G2Projective);
+define_curve_tests!(Bn254G2);
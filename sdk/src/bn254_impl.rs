@@ -1,12 +1,24 @@
 use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
-use ark_bn254::{Bn254, Fr, G1Projective};
+use ark_bn254::{Fr, G1Projective};
+
+/// Stealth keys in the G1 group of Bn254.
+pub struct Bn254;
 
 impl StealthAddressOnCurve for Bn254 {
     type Projective = G1Projective;
+    const NAME: &'static str = "bn254";
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(bn254, Bn254, Fr, G1Projective, 32, 32);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Bn254, Fr, G1Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Bn254, Fr, G1Projective);
 define_curve_tests!(Bn254);
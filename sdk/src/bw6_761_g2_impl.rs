@@ -0,0 +1,25 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the G2 group of the BW6_761 curve.
+use ark_bw6_761::{Fr, G2Projective};
+
+/// Stealth keys in the G2 group of BW6_761, as the G1 keys of `BW6_761` are to G1.
+pub struct BW6_761G2;
+
+impl StealthAddressOnCurve for BW6_761G2 {
+    type Projective = G2Projective;
+    const NAME: &'static str = "bw6_761_g2";
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(bw6_761_g2, BW6_761G2, Fr, G2Projective, 48, 96);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(BW6_761G2, Fr, G2Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(BW6_761G2, Fr, G2Projective);
+define_curve_tests!(BW6_761G2);
@@ -1,13 +1,25 @@
 use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
 /// Implementation of the StealthAddressOnCurve trait for the BW6_761 curve.
-use ark_bw6_761::{Fr, G1Projective, BW6_761};
+use ark_bw6_761::{Fr, G1Projective};
+
+/// Stealth keys in the G1 group of BW6_761.
+pub struct BW6_761;
 
 impl StealthAddressOnCurve for BW6_761 {
     type Projective = G1Projective;
+    const NAME: &'static str = "bw6_761";
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(bw6_761, BW6_761, Fr, G1Projective, 48, 96);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(BW6_761, Fr, G1Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(BW6_761, Fr, G1Projective);
 define_curve_tests!(BW6_761);
@@ -0,0 +1,546 @@
+//! Addresses of the chains that use the curves, so that a stealth public key can be rendered as a
+//! destination on the chain that uses its curve, rather than as a bare point.
+//!
+//! Each format implements `ChainAddressFormat` for the curves its chain uses:
+//!
+//! - `P2wpkh` and `P2tr`: Bitcoin's segwit v0 and taproot outputs, on secp256k1.
+//! - `Cosmos`: the bech32 `ripemd160(sha256(pk))` accounts of Cosmos SDK chains, on secp256k1.
+//! - `Filecoin`: `f1` addresses on secp256k1, and `f3` addresses on BLS12-381.
+//! - `Mina`: base58check public keys, on Pallas.
+//! - `Ethereum`: EIP-55 checksummed addresses, on secp256k1, with the `ethereum` feature.
+//! - `starknet::AccountClass`: the addresses of Starknet accounts, on the STARK curve.
+//!
+//! ```
+//! # #[cfg(feature = "secp256k1")]
+//! # {
+//! use stealth_address_kit::chain_address::{BitcoinNetwork, ChainAddressFormat, Cosmos, P2wpkh};
+//! use stealth_address_kit::prelude::*;
+//!
+//! let (_, spending_public_key) = Secp256k1::random_keypair();
+//! let (_, viewing_public_key) = Secp256k1::random_keypair();
+//! let (ephemeral_private_key, _) = Secp256k1::random_keypair();
+//! let (stealth_public_key, _) = Secp256k1::generate_stealth_address(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//! );
+//!
+//! let address = P2wpkh(BitcoinNetwork::Mainnet).address(&stealth_public_key);
+//! assert!(address.starts_with("bc1q"));
+//! let address = Cosmos::new("osmo").unwrap().address(&stealth_public_key);
+//! assert!(address.starts_with("osmo1"));
+//! # }
+//! ```
+use crate::prelude::PublicKey;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use bech32::Hrp;
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "secp256k1")]
+use crate::prelude::Fr;
+#[cfg(feature = "bls12_381")]
+use crate::Bls12_381;
+#[cfg(feature = "pallas")]
+use crate::Pallas;
+#[cfg(feature = "secp256k1")]
+use crate::Secp256k1;
+#[cfg(feature = "stark_curve")]
+use crate::StarkCurve;
+#[cfg(any(feature = "secp256k1", feature = "pallas"))]
+use ark_ec::CurveGroup;
+#[cfg(any(feature = "secp256k1", feature = "pallas"))]
+use ark_ff::{BigInteger, PrimeField};
+#[cfg(feature = "secp256k1")]
+use bech32::Bech32;
+#[cfg(feature = "secp256k1")]
+use ripemd::Ripemd160;
+#[cfg(feature = "secp256k1")]
+use sha2::{Digest, Sha256};
+
+/// A way of writing the public keys of the curve `C` as the addresses of a chain.
+pub trait ChainAddressFormat<C: StealthAddressOnCurve> {
+    fn address(&self, public_key: &PublicKey<C>) -> String;
+}
+
+/// `ripemd160(sha256(bytes))`, as Bitcoin and Cosmos hash public keys.
+#[cfg(feature = "secp256k1")]
+fn hash160(bytes: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(bytes)).into()
+}
+
+/// The networks of Bitcoin, which segwit addresses name with their prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitcoinNetwork {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+#[cfg(feature = "secp256k1")]
+impl BitcoinNetwork {
+    fn hrp(&self) -> Hrp {
+        match self {
+            BitcoinNetwork::Mainnet => bech32::hrp::BC,
+            BitcoinNetwork::Testnet => bech32::hrp::TB,
+            BitcoinNetwork::Regtest => bech32::hrp::BCRT,
+        }
+    }
+}
+
+/// Pay to witness public key hash: a segwit v0 output paying to the hash160 of the compressed
+/// public key, after BIP 173.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct P2wpkh(pub BitcoinNetwork);
+
+#[cfg(feature = "secp256k1")]
+impl ChainAddressFormat<Secp256k1> for P2wpkh {
+    fn address(&self, public_key: &PublicKey<Secp256k1>) -> String {
+        let program = hash160(&Secp256k1::encode_point(public_key));
+        bech32::segwit::encode_v0(self.0.hrp(), &program)
+            .expect("a key hash is a valid witness program")
+    }
+}
+
+/// Pay to taproot: a segwit v1 output whose key is the public key tweaked without a script tree,
+/// after BIP 86, as wallets derive their single-key taproot outputs.
+///
+/// The output is spent with a key-path signature by the private key `tweak_private_key` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct P2tr(pub BitcoinNetwork);
+
+/// The tagged hash of BIP 340, which prefixes the message with the hash of the tag, twice.
+#[cfg(feature = "secp256k1")]
+fn tagged_hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(tag.as_bytes());
+    Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+#[cfg(feature = "secp256k1")]
+impl P2tr {
+    /// The internal key with an even y, as BIP 340 lifts x-only keys, and its taproot tweak.
+    ///
+    /// The tweak is reduced by the order, which only changes the tweaks BIP 341 rejects, with a
+    /// negligible probability.
+    fn even_key_and_tweak(
+        public_key: &PublicKey<Secp256k1>,
+    ) -> (PublicKey<Secp256k1>, Fr<Secp256k1>) {
+        let affine = public_key.into_affine();
+        let even_key = if affine.y.into_bigint().is_odd() {
+            -*public_key
+        } else {
+            *public_key
+        };
+        let x = affine.x.into_bigint().to_bytes_be();
+        let tweak = Fr::<Secp256k1>::from_be_bytes_mod_order(&tagged_hash("TapTweak", &x));
+        (even_key, tweak)
+    }
+
+    /// The key of the output that pays to the public key.
+    pub fn output_key(public_key: &PublicKey<Secp256k1>) -> PublicKey<Secp256k1> {
+        let (even_key, tweak) = Self::even_key_and_tweak(public_key);
+        even_key + Secp256k1::derive_public_key(&tweak)
+    }
+
+    /// The private key of the output key, with which the recipient spends the output.
+    pub fn tweak_private_key(private_key: &Fr<Secp256k1>) -> Fr<Secp256k1> {
+        let public_key = Secp256k1::derive_public_key(private_key);
+        let (even_key, tweak) = Self::even_key_and_tweak(&public_key);
+        let even_private_key = if even_key == public_key {
+            *private_key
+        } else {
+            -*private_key
+        };
+        even_private_key + tweak
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl ChainAddressFormat<Secp256k1> for P2tr {
+    fn address(&self, public_key: &PublicKey<Secp256k1>) -> String {
+        let output_key = Self::output_key(public_key).into_affine();
+        let program = output_key.x.into_bigint().to_bytes_be();
+        bech32::segwit::encode_v1(self.0.hrp(), &program)
+            .expect("an x-only key is a valid witness program")
+    }
+}
+
+/// A prefix that is not a valid bech32 human-readable part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPrefix(pub String);
+
+impl Display for InvalidPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not a valid bech32 prefix: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidPrefix {}
+
+/// The accounts of a Cosmos SDK chain: the hash160 of the compressed public key in bech32, under
+/// the prefix of the chain, e.g. `cosmos` for the Cosmos Hub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cosmos {
+    prefix: Hrp,
+}
+
+impl Cosmos {
+    pub fn new(prefix: &str) -> Result<Self, InvalidPrefix> {
+        let prefix = Hrp::parse(prefix).map_err(|_| InvalidPrefix(prefix.to_owned()))?;
+        Ok(Cosmos { prefix })
+    }
+
+    /// The accounts of the Cosmos Hub.
+    pub fn hub() -> Self {
+        Cosmos::new("cosmos").expect("cosmos is a valid prefix")
+    }
+
+    pub fn prefix(&self) -> String {
+        self.prefix.to_lowercase()
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl ChainAddressFormat<Secp256k1> for Cosmos {
+    fn address(&self, public_key: &PublicKey<Secp256k1>) -> String {
+        let account = hash160(&Secp256k1::encode_point(public_key));
+        bech32::encode::<Bech32>(self.prefix, &account).expect("an account fits in a bech32 string")
+    }
+}
+
+/// The networks of Filecoin, which addresses name with their first letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilecoinNetwork {
+    Mainnet,
+    Testnet,
+}
+
+/// The addresses of Filecoin accounts: `f1` addresses, with the BLAKE2b-160 hash of the
+/// uncompressed secp256k1 public key, and `f3` addresses, with the compressed BLS12-381 public
+/// key itself. As it formats the keys of both curves, the curve is named when calling it, e.g.
+/// `ChainAddressFormat::<Bls12_381>::address(&Filecoin(FilecoinNetwork::Mainnet), &public_key)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Filecoin(pub FilecoinNetwork);
+
+fn blake2b<const N: usize>(input: &[u8]) -> [u8; N] {
+    use blake2::digest::{Update, VariableOutput};
+
+    let mut hasher = blake2::Blake2bVar::new(N).expect("BLAKE2b hashes are at most 64 bytes");
+    hasher.update(input);
+    let mut hash = [0; N];
+    hasher
+        .finalize_variable(&mut hash)
+        .expect("the buffer has the size of the hash");
+    hash
+}
+
+/// Writes bytes in the lowercase base32 of RFC 4648, without padding, as Filecoin does.
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut text = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        text.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+    text
+}
+
+impl Filecoin {
+    /// Writes the payload of an address of the protocol, followed by the BLAKE2b-32 checksum of
+    /// the protocol and payload.
+    #[cfg_attr(
+        not(any(feature = "secp256k1", feature = "bls12_381")),
+        allow(dead_code)
+    )]
+    fn encode(&self, protocol: u8, payload: &[u8]) -> String {
+        let network = match self.0 {
+            FilecoinNetwork::Mainnet => 'f',
+            FilecoinNetwork::Testnet => 't',
+        };
+        let checksum = blake2b::<4>(&[&[protocol], payload].concat());
+        format!(
+            "{network}{protocol}{}",
+            base32(&[payload, &checksum].concat())
+        )
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl ChainAddressFormat<Secp256k1> for Filecoin {
+    fn address(&self, public_key: &PublicKey<Secp256k1>) -> String {
+        let payload = blake2b::<20>(&Secp256k1::encode_point_uncompressed(public_key));
+        self.encode(1, &payload)
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+impl ChainAddressFormat<Bls12_381> for Filecoin {
+    fn address(&self, public_key: &PublicKey<Bls12_381>) -> String {
+        self.encode(3, &Bls12_381::encode_point(public_key))
+    }
+}
+
+/// The public keys of Mina, on Pallas: the x coordinate in little-endian and the parity of y, in
+/// base58check, after the version byte of public keys and the versions of their serialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mina;
+
+#[cfg(feature = "pallas")]
+fn mina_public_key(x: &[u8], is_odd: bool) -> String {
+    let mut payload = vec![0x01, 0x01];
+    payload.extend_from_slice(x);
+    payload.push(u8::from(is_odd));
+    bs58::encode(payload).with_check_version(0xcb).into_string()
+}
+
+#[cfg(feature = "pallas")]
+impl ChainAddressFormat<Pallas> for Mina {
+    fn address(&self, public_key: &PublicKey<Pallas>) -> String {
+        let affine = public_key.into_affine();
+        mina_public_key(
+            &affine.x.into_bigint().to_bytes_le(),
+            affine.y.into_bigint().is_odd(),
+        )
+    }
+}
+
+/// The EIP-55 checksummed address of the public key, as `ethereum::address_of` derives it.
+#[cfg(feature = "ethereum")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ethereum;
+
+#[cfg(feature = "ethereum")]
+impl ChainAddressFormat<Secp256k1> for Ethereum {
+    fn address(&self, public_key: &PublicKey<Secp256k1>) -> String {
+        crate::ethereum::checksum_address(&crate::ethereum::address_of(public_key))
+    }
+}
+
+/// The address of the account of the public key, as `0x` and 64 hexadecimal digits.
+#[cfg(feature = "stark_curve")]
+impl ChainAddressFormat<StarkCurve> for crate::starknet::AccountClass {
+    fn address(&self, public_key: &PublicKey<StarkCurve>) -> String {
+        crate::starknet::felt_to_hex(&self.account_address(public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base32_as_rfc_4648_does() {
+        let examples = [
+            ("", ""),
+            ("f", "my"),
+            ("fo", "mzxq"),
+            ("foo", "mzxw6"),
+            ("foob", "mzxw6yq"),
+            ("fooba", "mzxw6ytb"),
+            ("foobar", "mzxw6ytboi"),
+        ];
+        for (bytes, text) in examples {
+            assert_eq!(base32(bytes.as_bytes()), text);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_cosmos_prefixes() {
+        assert_eq!(Cosmos::hub().prefix(), "cosmos");
+        assert_eq!(
+            Cosmos::new("cos mos"),
+            Err(InvalidPrefix("cos mos".to_owned()))
+        );
+        assert!(Cosmos::new("").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod secp256k1_tests {
+    use super::*;
+    use ark_ec::Group;
+    use bech32::primitives::decode::CheckedHrpstring;
+
+    /// The public key of the private key 1, which BIP 173 pays to.
+    fn generator() -> PublicKey<Secp256k1> {
+        PublicKey::<Secp256k1>::generator()
+    }
+
+    #[test]
+    fn encodes_p2wpkh_as_bip_173_does() {
+        assert_eq!(
+            P2wpkh(BitcoinNetwork::Mainnet).address(&generator()),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            P2wpkh(BitcoinNetwork::Testnet).address(&generator()),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+    }
+
+    #[test]
+    fn encodes_p2tr_as_bip_86_does() {
+        // the first receiving address of the BIP 86 test vectors
+        let internal_key =
+            hex::decode("02cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115")
+                .unwrap();
+        let internal_key = Secp256k1::decode_point(&internal_key).unwrap();
+
+        assert_eq!(
+            hex::encode(&Secp256k1::encode_point(&P2tr::output_key(&internal_key))[1..]),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert_eq!(
+            P2tr(BitcoinNetwork::Mainnet).address(&internal_key),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        // the key with the odd y has the same output
+        assert_eq!(
+            P2tr(BitcoinNetwork::Mainnet).address(&-internal_key),
+            P2tr(BitcoinNetwork::Mainnet).address(&internal_key)
+        );
+    }
+
+    #[test]
+    fn tweaks_private_keys_to_spend_p2tr_outputs() {
+        for _ in 0..8 {
+            let (private_key, public_key) = Secp256k1::random_keypair();
+            let output_key = Secp256k1::derive_public_key(&P2tr::tweak_private_key(&private_key));
+            assert_eq!(output_key, P2tr::output_key(&public_key));
+        }
+    }
+
+    #[test]
+    fn encodes_cosmos_accounts() {
+        let address = Cosmos::hub().address(&generator());
+        let checked = CheckedHrpstring::new::<Bech32>(&address).unwrap();
+        assert_eq!(checked.hrp().to_lowercase(), "cosmos");
+        assert_eq!(
+            hex::encode(checked.byte_iter().collect::<Vec<_>>()),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn encodes_filecoin_f1_addresses() {
+        // the secp256k1 vector of the tests of go-address, Filecoin's reference implementation
+        let public_key = Secp256k1::decode_point_uncompressed(&[
+            4, 148, 2, 250, 195, 126, 100, 50, 164, 22, 163, 160, 202, 84, 38, 181, 24, 90, 179,
+            178, 79, 97, 52, 239, 162, 92, 228, 135, 200, 45, 46, 78, 19, 191, 69, 37, 17, 224,
+            210, 36, 84, 33, 248, 97, 59, 193, 13, 114, 250, 33, 102, 102, 169, 108, 59, 193, 57,
+            32, 211, 255, 35, 63, 208, 188, 5,
+        ])
+        .unwrap();
+        assert_eq!(
+            ChainAddressFormat::<Secp256k1>::address(
+                &Filecoin(FilecoinNetwork::Testnet),
+                &public_key
+            ),
+            "t15ihq5ibzwki2b4ep2f46avlkrqzhpqgtga7pdrq"
+        );
+        assert_eq!(
+            ChainAddressFormat::<Secp256k1>::address(
+                &Filecoin(FilecoinNetwork::Mainnet),
+                &public_key
+            ),
+            "f15ihq5ibzwki2b4ep2f46avlkrqzhpqgtga7pdrq"
+        );
+    }
+
+    #[cfg(feature = "ethereum")]
+    #[test]
+    fn encodes_ethereum_addresses() {
+        assert_eq!(
+            Ethereum.address(&generator()),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "bls12_381"))]
+mod bls12_381_tests {
+    use super::*;
+
+    #[test]
+    fn encodes_filecoin_f3_addresses() {
+        // the BLS vector of the tests of go-address, Filecoin's reference implementation
+        let public_key = Bls12_381::decode_point(&[
+            173, 88, 223, 105, 110, 45, 78, 145, 234, 134, 200, 129, 233, 56, 186, 78, 168, 27, 57,
+            94, 18, 121, 123, 132, 185, 207, 49, 75, 149, 70, 112, 94, 131, 156, 122, 153, 214, 6,
+            178, 71, 221, 180, 249, 172, 122, 52, 20, 221,
+        ])
+        .unwrap();
+        assert_eq!(
+            ChainAddressFormat::<Bls12_381>::address(
+                &Filecoin(FilecoinNetwork::Testnet),
+                &public_key
+            ),
+            "t3vvmn62lofvhjd2ugzca6sof2j2ubwok6cj4xxbfzz4yuxfkgobpihhd2thlanmsh3w2ptld2gqkn2jvlss4a"
+        );
+        assert_eq!(
+            ChainAddressFormat::<Bls12_381>::address(
+                &Filecoin(FilecoinNetwork::Mainnet),
+                &public_key
+            ),
+            "f3vvmn62lofvhjd2ugzca6sof2j2ubwok6cj4xxbfzz4yuxfkgobpihhd2thlanmsh3w2ptld2gqkn2jvlss4a"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "pallas"))]
+mod pallas_tests {
+    use super::*;
+
+    #[test]
+    fn encodes_mina_public_keys() {
+        // the empty public key of Mina, with a zero x, which is not on the curve
+        assert_eq!(
+            mina_public_key(&[0; 32], false),
+            "B62qiTKpEPjGTSHZrtM8uXiKgn8So916pLmNJKDhKeyBQL9TDb3nvBG"
+        );
+
+        let (_, public_key) = Pallas::random_keypair();
+        let address = Mina.address(&public_key);
+        assert!(address.starts_with("B62q"));
+        assert_eq!(address.len(), 55);
+
+        let decoded = bs58::decode(&address)
+            .with_check(Some(0xcb))
+            .into_vec()
+            .unwrap();
+        let affine = public_key.into_affine();
+        assert_eq!(decoded[3..35], affine.x.into_bigint().to_bytes_le());
+        assert_eq!(decoded[35], u8::from(affine.y.into_bigint().is_odd()));
+    }
+}
+
+#[cfg(all(test, feature = "stark_curve"))]
+mod stark_curve_tests {
+    use super::*;
+    use crate::starknet::{felt_to_hex, AccountClass, Felt};
+
+    #[test]
+    fn encodes_starknet_accounts() {
+        let account_class = AccountClass::new(Felt::from(7u8));
+        let (_, public_key) = StarkCurve::random_keypair();
+        let address = account_class.address(&public_key);
+        assert_eq!(
+            address,
+            felt_to_hex(&account_class.account_address(&public_key))
+        );
+        assert_eq!(address.len(), 66);
+    }
+}
@@ -0,0 +1,117 @@
+//! Serde support for keys and points, through their canonical compressed encodings, as 0x-prefixed
+//! hex in human-readable formats such as JSON, and as raw bytes in binary formats such as bincode
+//! and CBOR. Deserializing rejects any other encoding.
+//!
+//! The fields of other types can use it with `#[serde(with = "stealth_address_kit::compressed")]`,
+//! or, for points in the standard encoding of their curve as `MetaAddress` and `Announcement` are
+//! serialized, with `serialize_point` and `deserialize_point`:
+//!
+//! ```
+//! # #[cfg(feature = "secp256k1")]
+//! # {
+//! use serde::{Deserialize, Serialize};
+//! use stealth_address_kit::prelude::*;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Keys {
+//!     #[serde(with = "stealth_address_kit::compressed")]
+//!     viewing_key: SecretKey<Secp256k1>,
+//!     #[serde(
+//!         serialize_with = "stealth_address_kit::compressed::serialize_point::<Secp256k1, _>",
+//!         deserialize_with = "stealth_address_kit::compressed::deserialize_point::<Secp256k1, _>"
+//!     )]
+//!     spending_public_key: PublicKey<Secp256k1>,
+//! }
+//! # }
+//! ```
+use crate::encoding::{decode, encode};
+use crate::StealthAddressOnCurve;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt::Formatter;
+
+pub fn serialize<T: CanonicalSerialize, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_encoding(&encode(value), serializer)
+}
+
+pub fn deserialize<'de, T: CanonicalDeserialize + CanonicalSerialize, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    decode(&deserialize_encoding(deserializer, 0)?).map_err(D::Error::custom)
+}
+
+/// Serializes a point in the standard compressed encoding of its curve, as `encode_point`
+/// encodes it, for use with `#[serde(serialize_with = "...::serialize_point::<C, _>")]`.
+pub fn serialize_point<C: StealthAddressOnCurve, S: Serializer>(
+    point: &C::Projective,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_encoding(&C::encode_point(point), serializer)
+}
+
+/// Deserializes a point in the standard compressed encoding of its curve, for use with
+/// `#[serde(deserialize_with = "...::deserialize_point::<C, _>")]`.
+pub fn deserialize_point<'de, C: StealthAddressOnCurve, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<C::Projective, D::Error> {
+    C::decode_point(&deserialize_encoding(
+        deserializer,
+        C::UNCOMPRESSED_POINT_SIZE,
+    )?)
+    .map_err(D::Error::custom)
+}
+
+fn serialize_encoding<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes the bytes of an encoding, reserving no more than `max_size` bytes up front
+/// whatever length a binary format announces.
+fn deserialize_encoding<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    max_size: usize,
+) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let value = String::deserialize(deserializer)?;
+        hex::decode(value.strip_prefix("0x").unwrap_or(&value)).map_err(D::Error::custom)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor { max_size })
+    }
+}
+
+struct BytesVisitor {
+    max_size: usize,
+}
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a compressed key or point")
+    }
+
+    fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(bytes)
+    }
+
+    // for formats that write bytes as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(self.max_size));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,478 @@
+use crate::encoding::{decode, encode, InvalidEncoding};
+use crate::meta_address::InvalidMetaAddress;
+use crate::prelude::Fr;
+use crate::stealth_addresses::{HasViewTag, StealthAddressOnCurve};
+use std::marker::PhantomData;
+
+/// The operations of `StealthAddressOnCurve`, on private keys in their canonical encoding and
+/// points in the standard compressed encoding of the curve, as `encode_point` encodes them, so
+/// that the curve can be picked at runtime, e.g. from a meta-address.
+///
+/// Malformed or non-canonical keys and points are rejected with `InvalidEncoding`.
+pub trait DynStealthCurve: Send + Sync {
+    /// The name of the curve, after its cargo feature, with a `_g2` suffix for the G2 groups of
+    /// the pairing curves.
+    fn name(&self) -> &'static str;
+
+    /// The ERC-5564 scheme id of the curve, if it has one.
+    fn scheme_id(&self) -> Option<u64>;
+
+    /// The size of an encoded private key.
+    fn private_key_size(&self) -> usize;
+
+    /// The size of an encoded public key or stealth address.
+    fn public_key_size(&self) -> usize;
+
+    fn generate_random_fr(&self) -> Vec<u8>;
+
+    /// Returns a private key and its public key.
+    fn random_keypair(&self) -> (Vec<u8>, Vec<u8>);
+
+    fn derive_public_key(&self, private_key: &[u8]) -> Result<Vec<u8>, InvalidEncoding>;
+
+    /// Checks that a public key is a point of the curve, in its standard compressed encoding.
+    fn check_public_key(&self, public_key: &[u8]) -> Result<(), InvalidEncoding>;
+
+    /// Parses an ERC-5564 style `st:<chain>:0x<spending public key><viewing public key>`
+    /// meta-address into its chain and public keys, checking that both keys are on the curve.
+    fn parse_meta_address<'a>(
+        &self,
+        meta_address: &'a str,
+    ) -> Result<(&'a str, Vec<u8>, Vec<u8>), InvalidMetaAddress> {
+        let mut parts = meta_address.splitn(3, ':');
+        if parts.next() != Some("st") {
+            return Err(InvalidMetaAddress::Malformed);
+        }
+        let chain = parts.next().ok_or(InvalidMetaAddress::Malformed)?;
+        let keys = parts
+            .next()
+            .and_then(|keys| keys.strip_prefix("0x"))
+            .and_then(|keys| hex::decode(keys).ok())
+            .ok_or(InvalidMetaAddress::Malformed)?;
+
+        let point_size = self.public_key_size();
+        if keys.len() != 2 * point_size {
+            return Err(InvalidMetaAddress::InvalidEncoding);
+        }
+        let (spending_public_key, viewing_public_key) = keys.split_at(point_size);
+        for public_key in [spending_public_key, viewing_public_key] {
+            self.check_public_key(public_key)
+                .map_err(|_| InvalidMetaAddress::InvalidEncoding)?;
+        }
+        Ok((
+            chain,
+            spending_public_key.to_vec(),
+            viewing_public_key.to_vec(),
+        ))
+    }
+
+    /// Returns the stealth address and its view tag.
+    fn generate_stealth_address(
+        &self,
+        viewing_public_key: &[u8],
+        spending_public_key: &[u8],
+        ephemeral_private_key: &[u8],
+    ) -> Result<(Vec<u8>, u64), InvalidEncoding>;
+
+    /// Returns the stealth private key, if the view tag matches.
+    fn generate_stealth_private_key(
+        &self,
+        ephemeral_public_key: &[u8],
+        viewing_key: &[u8],
+        spending_key: &[u8],
+        expected_view_tag: u64,
+    ) -> Result<Option<Vec<u8>>, InvalidEncoding>;
+
+    /// Returns the stealth address the announcement must carry, if the view tag matches.
+    fn check_stealth_address(
+        &self,
+        ephemeral_public_key: &[u8],
+        viewing_key: &[u8],
+        spending_public_key: &[u8],
+        expected_view_tag: u64,
+    ) -> Result<Option<Vec<u8>>, InvalidEncoding>;
+
+    /// Returns the index and stealth private key of every announcement, given as its stealth
+    /// address, ephemeral public key and view tag, that is addressed to the keys.
+    fn scan(
+        &self,
+        announcements: &[(Vec<u8>, Vec<u8>, u64)],
+        viewing_key: &[u8],
+        spending_key: &[u8],
+    ) -> Result<Vec<(usize, Vec<u8>)>, InvalidEncoding>;
+}
+
+/// Implements `DynStealthCurve` for a `StealthAddressOnCurve` implementation, so that curves
+/// outside the sdk can be used alongside the ones in `curves()`.
+///
+/// ```
+/// # #[cfg(feature = "secp256k1")]
+/// # {
+/// use stealth_address_kit::{DynCurve, DynStealthCurve, StealthCurve};
+///
+/// static MY_CURVE: DynCurve<StealthCurve<ark_secp256k1::Projective>> =
+///     DynCurve::new("my_curve", None);
+///
+/// let (private_key, public_key) = MY_CURVE.random_keypair();
+/// assert_eq!(MY_CURVE.derive_public_key(&private_key).unwrap(), public_key);
+/// # }
+/// ```
+pub struct DynCurve<C> {
+    name: &'static str,
+    scheme_id: Option<u64>,
+    curve: PhantomData<fn() -> C>,
+}
+
+impl<C> DynCurve<C> {
+    pub const fn new(name: &'static str, scheme_id: Option<u64>) -> Self {
+        DynCurve {
+            name,
+            scheme_id,
+            curve: PhantomData,
+        }
+    }
+}
+
+impl<C: StealthAddressOnCurve> DynCurve<C> {
+    /// Wraps the curve under its own `NAME` and `SCHEME_ID`.
+    pub const fn from_curve() -> Self {
+        DynCurve::new(C::NAME, C::SCHEME_ID)
+    }
+}
+
+impl<C: StealthAddressOnCurve> DynStealthCurve for DynCurve<C>
+where
+    Fr<C>: HasViewTag,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn scheme_id(&self) -> Option<u64> {
+        self.scheme_id
+    }
+
+    fn private_key_size(&self) -> usize {
+        C::SCALAR_SIZE
+    }
+
+    fn public_key_size(&self) -> usize {
+        C::COMPRESSED_POINT_SIZE
+    }
+
+    fn generate_random_fr(&self) -> Vec<u8> {
+        encode(&C::generate_random_fr())
+    }
+
+    fn random_keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        let (private_key, public_key) = C::random_keypair();
+        (encode(&private_key), C::encode_point(&public_key))
+    }
+
+    fn derive_public_key(&self, private_key: &[u8]) -> Result<Vec<u8>, InvalidEncoding> {
+        let private_key = decode(private_key)?;
+        Ok(C::encode_point(&C::derive_public_key(&private_key)))
+    }
+
+    fn check_public_key(&self, public_key: &[u8]) -> Result<(), InvalidEncoding> {
+        C::decode_point(public_key).map(|_| ())
+    }
+
+    fn generate_stealth_address(
+        &self,
+        viewing_public_key: &[u8],
+        spending_public_key: &[u8],
+        ephemeral_private_key: &[u8],
+    ) -> Result<(Vec<u8>, u64), InvalidEncoding> {
+        let (stealth_address, view_tag) = C::generate_stealth_address(
+            C::decode_point(viewing_public_key)?,
+            C::decode_point(spending_public_key)?,
+            decode(ephemeral_private_key)?,
+        );
+        Ok((C::encode_point(&stealth_address), view_tag))
+    }
+
+    fn generate_stealth_private_key(
+        &self,
+        ephemeral_public_key: &[u8],
+        viewing_key: &[u8],
+        spending_key: &[u8],
+        expected_view_tag: u64,
+    ) -> Result<Option<Vec<u8>>, InvalidEncoding> {
+        Ok(C::generate_stealth_private_key(
+            C::decode_point(ephemeral_public_key)?,
+            decode(viewing_key)?,
+            decode(spending_key)?,
+            expected_view_tag,
+        )
+        .map(|stealth_private_key| encode(&stealth_private_key)))
+    }
+
+    fn check_stealth_address(
+        &self,
+        ephemeral_public_key: &[u8],
+        viewing_key: &[u8],
+        spending_public_key: &[u8],
+        expected_view_tag: u64,
+    ) -> Result<Option<Vec<u8>>, InvalidEncoding> {
+        Ok(C::check_stealth_address(
+            C::decode_point(ephemeral_public_key)?,
+            decode(viewing_key)?,
+            C::decode_point(spending_public_key)?,
+            expected_view_tag,
+        )
+        .map(|stealth_address| C::encode_point(&stealth_address)))
+    }
+
+    fn scan(
+        &self,
+        announcements: &[(Vec<u8>, Vec<u8>, u64)],
+        viewing_key: &[u8],
+        spending_key: &[u8],
+    ) -> Result<Vec<(usize, Vec<u8>)>, InvalidEncoding> {
+        let announcements = announcements
+            .iter()
+            .map(|(stealth_address, ephemeral_public_key, view_tag)| {
+                Ok((
+                    C::decode_point(stealth_address)?,
+                    C::decode_point(ephemeral_public_key)?,
+                    *view_tag,
+                ))
+            })
+            .collect::<Result<Vec<_>, InvalidEncoding>>()?;
+
+        Ok(
+            C::scan(&announcements, decode(viewing_key)?, decode(spending_key)?)
+                .into_iter()
+                .map(|(index, stealth_private_key)| (index, encode(&stealth_private_key)))
+                .collect(),
+        )
+    }
+}
+
+static CURVES: &[&dyn DynStealthCurve] = &[
+    #[cfg(feature = "baby_jub_jub")]
+    &DynCurve::<crate::BabyJubJub>::from_curve(),
+    #[cfg(feature = "bandersnatch")]
+    &DynCurve::<crate::Bandersnatch>::from_curve(),
+    #[cfg(feature = "bls12_377")]
+    &DynCurve::<crate::Bls12_377>::from_curve(),
+    #[cfg(feature = "bls12_377")]
+    &DynCurve::<crate::Bls12_377G2>::from_curve(),
+    #[cfg(feature = "bls12_381")]
+    &DynCurve::<crate::Bls12_381>::from_curve(),
+    #[cfg(feature = "bls12_381")]
+    &DynCurve::<crate::Bls12_381G2>::from_curve(),
+    #[cfg(feature = "bn254")]
+    &DynCurve::<crate::Bn254>::from_curve(),
+    #[cfg(feature = "bn254")]
+    &DynCurve::<crate::Bn254G2>::from_curve(),
+    #[cfg(feature = "bw6_761")]
+    &DynCurve::<crate::BW6_761>::from_curve(),
+    #[cfg(feature = "bw6_761")]
+    &DynCurve::<crate::BW6_761G2>::from_curve(),
+    #[cfg(feature = "ed25519")]
+    &DynCurve::<crate::Ed25519>::from_curve(),
+    #[cfg(feature = "ed_on_bls12_377")]
+    &DynCurve::<crate::EdOnBls12_377>::from_curve(),
+    #[cfg(feature = "grumpkin")]
+    &DynCurve::<crate::Grumpkin>::from_curve(),
+    #[cfg(feature = "jubjub")]
+    &DynCurve::<crate::Jubjub>::from_curve(),
+    #[cfg(feature = "pallas")]
+    &DynCurve::<crate::Pallas>::from_curve(),
+    #[cfg(feature = "ristretto255")]
+    &DynCurve::<crate::Ristretto255>::from_curve(),
+    #[cfg(feature = "secp256k1")]
+    &DynCurve::<crate::Secp256k1>::from_curve(),
+    #[cfg(feature = "secp256r1")]
+    &DynCurve::<crate::Secp256r1>::from_curve(),
+    #[cfg(feature = "secq256k1")]
+    &DynCurve::<crate::Secq256k1>::from_curve(),
+    #[cfg(feature = "stark_curve")]
+    &DynCurve::<crate::StarkCurve>::from_curve(),
+    #[cfg(feature = "vesta")]
+    &DynCurve::<crate::Vesta>::from_curve(),
+];
+
+/// The compiled-in curves, sorted by name.
+pub fn curves() -> &'static [&'static dyn DynStealthCurve] {
+    CURVES
+}
+
+/// Returns the compiled-in curve with the given name, as listed by `curves()`.
+pub fn find_curve(name: &str) -> Option<&'static dyn DynStealthCurve> {
+    CURVES.iter().find(|curve| curve.name() == name).copied()
+}
+
+/// Returns the compiled-in curve with the given ERC-5564 scheme id.
+pub fn find_curve_by_scheme_id(scheme_id: u64) -> Option<&'static dyn DynStealthCurve> {
+    CURVES
+        .iter()
+        .find(|curve| curve.scheme_id() == Some(scheme_id))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curves_are_sorted_by_unique_name() {
+        let names: Vec<_> = curves().iter().map(|curve| curve.name()).collect();
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+        for name in names {
+            assert_eq!(find_curve(name).unwrap().name(), name);
+        }
+        assert!(find_curve("ed448").is_none());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn find_curve_by_scheme_id_happy_path() {
+        assert_eq!(find_curve_by_scheme_id(1).unwrap().name(), "secp256k1");
+        assert!(find_curve_by_scheme_id(0).is_none());
+    }
+
+    #[test]
+    fn round_trip_on_every_curve() {
+        for curve in curves() {
+            let (spending_key, spending_public_key) = curve.random_keypair();
+            let (viewing_key, viewing_public_key) = curve.random_keypair();
+            let (ephemeral_private_key, ephemeral_public_key) = curve.random_keypair();
+            assert_eq!(spending_key.len(), curve.private_key_size());
+            assert_eq!(spending_public_key.len(), curve.public_key_size());
+            assert_eq!(
+                curve.generate_random_fr().len(),
+                curve.private_key_size(),
+                "{}",
+                curve.name()
+            );
+
+            let (stealth_address, view_tag) = curve
+                .generate_stealth_address(
+                    &viewing_public_key,
+                    &spending_public_key,
+                    &ephemeral_private_key,
+                )
+                .unwrap();
+            assert_eq!(
+                curve
+                    .check_stealth_address(
+                        &ephemeral_public_key,
+                        &viewing_key,
+                        &spending_public_key,
+                        view_tag
+                    )
+                    .unwrap(),
+                Some(stealth_address.clone()),
+                "{}",
+                curve.name()
+            );
+
+            let stealth_private_key = curve
+                .generate_stealth_private_key(
+                    &ephemeral_public_key,
+                    &viewing_key,
+                    &spending_key,
+                    view_tag,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                curve.derive_public_key(&stealth_private_key).unwrap(),
+                stealth_address,
+                "{}",
+                curve.name()
+            );
+            assert_eq!(
+                curve
+                    .scan(
+                        &[
+                            (
+                                stealth_address.clone(),
+                                ephemeral_public_key.clone(),
+                                view_tag.wrapping_add(1)
+                            ),
+                            (
+                                spending_public_key.clone(),
+                                ephemeral_public_key.clone(),
+                                view_tag
+                            ),
+                            (stealth_address, ephemeral_public_key, view_tag)
+                        ],
+                        &viewing_key,
+                        &spending_key
+                    )
+                    .unwrap(),
+                [(2, stealth_private_key)]
+            );
+        }
+    }
+
+    #[test]
+    fn parses_meta_addresses() {
+        for curve in curves() {
+            let (_, spending_public_key) = curve.random_keypair();
+            let (_, viewing_public_key) = curve.random_keypair();
+            let keys = hex::encode([&spending_public_key[..], &viewing_public_key].concat());
+            assert_eq!(
+                curve.parse_meta_address(&format!("st:eth:0x{keys}")),
+                Ok(("eth", spending_public_key.clone(), viewing_public_key)),
+                "{}",
+                curve.name()
+            );
+
+            for malformed in [
+                format!("sk:eth:0x{keys}"),
+                format!("st:eth:{keys}"),
+                format!("st:eth:0x{keys}0"),
+                "st".to_owned(),
+                format!("st:0x{keys}"),
+            ] {
+                assert_eq!(
+                    curve.parse_meta_address(&malformed),
+                    Err(InvalidMetaAddress::Malformed),
+                    "{malformed}"
+                );
+            }
+            let off_curve = hex::encode(vec![0xff; spending_public_key.len()]);
+            for keys in [
+                hex::encode(&spending_public_key),
+                format!("{}{off_curve}", hex::encode(&spending_public_key)),
+            ] {
+                assert_eq!(
+                    curve.parse_meta_address(&format!("st:eth:0x{keys}")),
+                    Err(InvalidMetaAddress::InvalidEncoding),
+                    "{}",
+                    curve.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_keys_are_rejected() {
+        for curve in curves() {
+            let (private_key, public_key) = curve.random_keypair();
+            assert_eq!(curve.derive_public_key(&[]), Err(InvalidEncoding));
+            assert_eq!(
+                curve.derive_public_key(&[private_key.as_slice(), &[0]].concat()),
+                Err(InvalidEncoding)
+            );
+            assert_eq!(
+                curve.generate_stealth_address(&public_key, &public_key[1..], &private_key),
+                Err(InvalidEncoding),
+                "{}",
+                curve.name()
+            );
+            assert_eq!(curve.check_public_key(&public_key), Ok(()));
+            assert_eq!(
+                curve.check_public_key(&vec![0xff; public_key.len()]),
+                Err(InvalidEncoding),
+                "{}",
+                curve.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{
+    define_curve_tests, encoding::InvalidEncoding, stealth_addresses::StealthAddressOnCurve,
+};
+/// Implementation of the StealthAddressOnCurve trait for the Ed25519 curve.
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ed25519::{EdwardsAffine, EdwardsProjective, Fq, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use sha2::{Digest, Sha512};
+
+/// The size of an RFC 8032 encoded point.
+const POINT_SIZE: usize = 32;
+/// The size of an Ed25519 signature.
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// Domain separation for the signing nonces, which cannot come from an expanded seed.
+const NONCE_DOMAIN: &[u8] = b"stealth_address_kit/ed25519/nonce";
+
+pub struct Ed25519;
+
+impl StealthAddressOnCurve for Ed25519 {
+    type Projective = EdwardsProjective;
+    const NAME: &'static str = "ed25519";
+    const COMPRESSED_POINT_SIZE: usize = 32;
+    const UNCOMPRESSED_POINT_SIZE: usize = 64;
+
+    /// Encodes a point as specified by RFC 8032, i.e. as its y coordinate with the
+    /// parity of x in the most significant bit. This is the public key format of
+    /// Ed25519 wallets and signature verifiers.
+    fn encode_point(point: &EdwardsProjective) -> Vec<u8> {
+        let affine = point.into_affine();
+        let mut bytes = affine.y.into_bigint().to_bytes_le();
+        if affine.x.into_bigint().is_odd() {
+            bytes[POINT_SIZE - 1] |= 0x80;
+        }
+        bytes
+    }
+
+    /// Decodes an RFC 8032 encoded point, rejecting non-canonical encodings and
+    /// points outside of the prime order subgroup.
+    fn decode_point(bytes: &[u8]) -> Result<EdwardsProjective, InvalidEncoding> {
+        let bytes: &[u8; POINT_SIZE] = bytes.try_into().map_err(|_| InvalidEncoding)?;
+        let x_is_odd = bytes[POINT_SIZE - 1] & 0x80 != 0;
+        let mut y_bytes = *bytes;
+        y_bytes[POINT_SIZE - 1] &= 0x7f;
+        let y = Fq::from_le_bytes_mod_order(&y_bytes);
+        if y.into_bigint().to_bytes_le() != y_bytes {
+            return Err(InvalidEncoding);
+        }
+
+        let (x, neg_x) = EdwardsAffine::get_xs_from_y_unchecked(y).ok_or(InvalidEncoding)?;
+        let x = if x.into_bigint().is_odd() == x_is_odd {
+            x
+        } else {
+            neg_x
+        };
+        if x.into_bigint().is_odd() != x_is_odd {
+            // x is zero, which has no odd representation
+            return Err(InvalidEncoding);
+        }
+
+        let point = EdwardsAffine::new_unchecked(x, y);
+        if !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(InvalidEncoding);
+        }
+        Ok(point.into_group())
+    }
+
+    /// Computes the shared point with the cofactor cleared, so that a public key carrying a
+    /// small-order component cannot leak bits of the private key through the shared point.
+    #[inline]
+    fn compute_shared_point(private_key: Fr, public_key: EdwardsProjective) -> EdwardsProjective {
+        (public_key * private_key)
+            .into_affine()
+            .mul_by_cofactor_to_group()
+    }
+}
+
+fn scalar_to_bytes(scalar: &Fr) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&scalar.into_bigint().to_bytes_le());
+    bytes
+}
+
+/// Hashes the inputs with SHA-512 into a scalar.
+fn hash_to_scalar(inputs: &[&[u8]]) -> Fr {
+    let mut hasher = Sha512::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    Fr::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+impl Ed25519 {
+    /// Signs a message with a raw private scalar, such as a stealth private key.
+    ///
+    /// Stealth private keys are scalars derived from the spending key, not the 32-byte seeds
+    /// that RFC 8032 signing expands, so the nonce is derived deterministically from the
+    /// scalar and the message instead. The signature verifies as a standard Ed25519 signature
+    /// under the RFC 8032 encoding of the public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `private_key` - The private scalar.
+    /// * `message` - The message to sign.
+    ///
+    /// # Returns
+    ///
+    /// The signature, as the encoded nonce point followed by the little-endian scalar.
+    pub fn sign(private_key: &Fr, message: &[u8]) -> [u8; SIGNATURE_SIZE] {
+        let public_key = Self::encode_point(&Self::derive_public_key(private_key));
+        let nonce = hash_to_scalar(&[NONCE_DOMAIN, &scalar_to_bytes(private_key), message]);
+        let r = Self::encode_point(&(EdwardsProjective::generator() * nonce));
+        let challenge = hash_to_scalar(&[&r, &public_key, message]);
+        let s = nonce + challenge * private_key;
+
+        let mut signature = [0u8; SIGNATURE_SIZE];
+        signature[..POINT_SIZE].copy_from_slice(&r);
+        signature[POINT_SIZE..].copy_from_slice(&scalar_to_bytes(&s));
+        signature
+    }
+
+    /// Verifies an Ed25519 signature, rejecting non-canonical encodings of its scalar and
+    /// nonce point.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - The public key of the signer.
+    /// * `message` - The signed message.
+    /// * `signature` - The signature.
+    ///
+    /// # Returns
+    ///
+    /// Whether the signature is valid.
+    pub fn verify(
+        public_key: &EdwardsProjective,
+        message: &[u8],
+        signature: &[u8; SIGNATURE_SIZE],
+    ) -> bool {
+        let (r_bytes, s_bytes) = signature.split_at(POINT_SIZE);
+        let s = Fr::from_le_bytes_mod_order(s_bytes);
+        if scalar_to_bytes(&s) != s_bytes {
+            return false;
+        }
+        let Ok(r) = Self::decode_point(r_bytes) else {
+            return false;
+        };
+
+        let challenge = hash_to_scalar(&[r_bytes, &Self::encode_point(public_key), message]);
+        EdwardsProjective::generator() * s == r + *public_key * challenge
+    }
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(ed25519, Ed25519, Fr, EdwardsProjective, 32, 32);
+
+#[cfg(feature = "ffi")]
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug)]
+pub struct ed25519Signature([u8; SIGNATURE_SIZE]);
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn ed25519_ffi_sign(
+    private_key: *mut ed25519Fr,
+    message: *const u8,
+    message_len: usize,
+) -> *mut CReturn<ed25519Signature> {
+    let invalid = || {
+        Box::into_raw(Box::new(CReturn {
+            value: ed25519Signature([0u8; SIGNATURE_SIZE]),
+            err_code: CErrorCode::InvalidKeys,
+        }))
+    };
+    if private_key.is_null() || (message.is_null() && message_len != 0) {
+        return invalid();
+    }
+    let private_key: Fr = match unsafe { &*private_key }.try_into() {
+        Ok(v) => v,
+        Err(_) => return invalid(),
+    };
+    let message = if message_len == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(message, message_len) }
+    };
+
+    Box::into_raw(Box::new(CReturn {
+        value: ed25519Signature(Ed25519::sign(&private_key, message)),
+        err_code: CErrorCode::NoError,
+    }))
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn drop_ed25519_ffi_sign(ptr: *mut CReturn<ed25519Signature>) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(ptr);
+    }
+}
+
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Ed25519, Fr, EdwardsProjective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Ed25519, Fr, EdwardsProjective);
+define_curve_tests!(Ed25519);
+
+#[cfg(test)]
+mod signature_tests {
+    use super::*;
+    use ark_ff::Zero;
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+    /// The scalar that RFC 8032 expands a seed into.
+    fn expanded_scalar(seed: &[u8; 32]) -> Fr {
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&Sha512::digest(seed)[..32]);
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+        Fr::from_le_bytes_mod_order(&scalar)
+    }
+
+    #[test]
+    fn encodes_points_like_rfc_8032() {
+        let seed = [7u8; 32];
+        let public_key = Ed25519::derive_public_key(&expanded_scalar(&seed));
+        let expected = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+
+        assert_eq!(Ed25519::encode_point(&public_key), expected);
+        assert_eq!(Ed25519::decode_point(&expected), Ok(public_key));
+    }
+
+    #[test]
+    fn rejects_bad_point_encodings() {
+        // y = p, which is not canonical
+        let mut non_canonical = [0xff; 32];
+        non_canonical[0] = 0xed;
+        non_canonical[31] = 0x7f;
+        assert_eq!(Ed25519::decode_point(&non_canonical), Err(InvalidEncoding));
+
+        // the identity, with the parity bit of its zero x set
+        let mut negative_zero = [0u8; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+        assert_eq!(Ed25519::decode_point(&negative_zero), Err(InvalidEncoding));
+
+        // a point of order 2
+        let mut order_two = [0u8; 32];
+        order_two.copy_from_slice(&(-Fq::from(1u8)).into_bigint().to_bytes_le());
+        assert_eq!(Ed25519::decode_point(&order_two), Err(InvalidEncoding));
+    }
+
+    #[test]
+    fn stealth_key_signatures_verify_as_ed25519() {
+        let (spending_key, spending_public_key) = Ed25519::random_keypair();
+        let (viewing_key, viewing_public_key) = Ed25519::random_keypair();
+        let (ephemeral_private_key, ephemeral_public_key) = Ed25519::random_keypair();
+        let (stealth_address, view_tag) = Ed25519::generate_stealth_address(
+            viewing_public_key,
+            spending_public_key,
+            ephemeral_private_key,
+        );
+        let stealth_private_key = Ed25519::generate_stealth_private_key(
+            ephemeral_public_key,
+            viewing_key,
+            spending_key,
+            view_tag,
+        )
+        .unwrap();
+
+        let message = b"transfer 1 SOL";
+        let signature = Ed25519::sign(&stealth_private_key, message);
+        assert!(Ed25519::verify(&stealth_address, message, &signature));
+        assert!(!Ed25519::verify(
+            &stealth_address,
+            b"transfer 2 SOL",
+            &signature
+        ));
+        assert!(!Ed25519::verify(&spending_public_key, message, &signature));
+
+        let verifying_key =
+            VerifyingKey::from_bytes(&Ed25519::encode_point(&stealth_address).try_into().unwrap())
+                .unwrap();
+        let signature = Signature::from_bytes(&signature);
+        assert!(verifying_key.verify_strict(message, &signature).is_ok());
+    }
+
+    #[test]
+    fn verifies_rfc_8032_signatures() {
+        let signing_key = SigningKey::from_bytes(&[42u8; 32]);
+        let public_key = Ed25519::decode_point(&signing_key.verifying_key().to_bytes()).unwrap();
+        let message = b"hello";
+        let signature = signing_key.sign(message).to_bytes();
+        assert!(Ed25519::verify(&public_key, message, &signature));
+        assert!(signing_key
+            .verifying_key()
+            .verify(message, &Signature::from_bytes(&signature))
+            .is_ok());
+
+        // adding the group order to the scalar keeps the equation but is not canonical
+        let mut malleated = signature;
+        let s = Fr::from_le_bytes_mod_order(&signature[32..]);
+        let mut s_plus_order = s.into_bigint();
+        s_plus_order.add_with_carry(&Fr::MODULUS);
+        malleated[32..].copy_from_slice(&s_plus_order.to_bytes_le());
+        assert!(!Ed25519::verify(&public_key, message, &malleated));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn ffi_sign_matches_rust() {
+        let (private_key, public_key) = Ed25519::random_keypair();
+        let message = b"hello";
+        let mut ffi_private_key = ed25519Fr::try_from(private_key).unwrap();
+
+        let result = ed25519_ffi_sign(&mut ffi_private_key, message.as_ptr(), message.len());
+        let signature = unsafe { &*result };
+        assert_eq!(signature.err_code, CErrorCode::NoError);
+        assert_eq!(signature.value.0, Ed25519::sign(&private_key, message));
+        assert!(Ed25519::verify(&public_key, message, &signature.value.0));
+        drop_ed25519_ffi_sign(result);
+
+        let result = ed25519_ffi_sign(std::ptr::null_mut(), message.as_ptr(), message.len());
+        assert_eq!(unsafe { &*result }.err_code, CErrorCode::InvalidKeys);
+        drop_ed25519_ffi_sign(result);
+    }
+
+    #[test]
+    fn shared_point_ignores_small_order_components() {
+        let (private_key, public_key) = Ed25519::random_keypair();
+        // a point of order 2
+        let torsion = EdwardsAffine::new_unchecked(Fq::zero(), -Fq::from(1u8));
+        assert!(!torsion.is_in_correct_subgroup_assuming_on_curve());
+
+        assert_eq!(
+            Ed25519::compute_shared_point(private_key, public_key + torsion),
+            Ed25519::compute_shared_point(private_key, public_key)
+        );
+    }
+}
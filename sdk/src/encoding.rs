@@ -0,0 +1,52 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::fmt::{Display, Formatter};
+
+/// A key or point that is not a canonical encoding for the curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEncoding;
+
+impl Display for InvalidEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not a canonical encoding of a key or point")
+    }
+}
+
+impl std::error::Error for InvalidEncoding {}
+
+pub(crate) fn encode<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a vec cannot fail");
+    bytes
+}
+
+/// Decodes a compressed key or point, only accepting its canonical encoding.
+pub(crate) fn decode<T: CanonicalDeserialize + CanonicalSerialize>(
+    bytes: &[u8],
+) -> Result<T, InvalidEncoding> {
+    let decoded = T::deserialize_compressed(bytes).map_err(|_| InvalidEncoding)?;
+    if encode(&decoded) != bytes {
+        return Err(InvalidEncoding);
+    }
+    Ok(decoded)
+}
+
+pub(crate) fn encode_uncompressed<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.uncompressed_size());
+    value
+        .serialize_uncompressed(&mut bytes)
+        .expect("serializing into a vec cannot fail");
+    bytes
+}
+
+/// Decodes an uncompressed point, only accepting its canonical encoding.
+pub(crate) fn decode_uncompressed<T: CanonicalDeserialize + CanonicalSerialize>(
+    bytes: &[u8],
+) -> Result<T, InvalidEncoding> {
+    let decoded = T::deserialize_uncompressed(bytes).map_err(|_| InvalidEncoding)?;
+    if encode_uncompressed(&decoded) != bytes {
+        return Err(InvalidEncoding);
+    }
+    Ok(decoded)
+}
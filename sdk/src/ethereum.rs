@@ -0,0 +1,122 @@
+//! Stealth payments on Ethereum, where ERC-5564 scheme id 1 uses secp256k1 keys, and stealth
+//! addresses are the Ethereum addresses of the stealth public keys.
+//!
+//! ```
+//! use stealth_address_kit::ethereum::address_of;
+//! use stealth_address_kit::prelude::*;
+//!
+//! let (_, spending_public_key) = Secp256k1::random_keypair();
+//! let (_, viewing_public_key) = Secp256k1::random_keypair();
+//! let (ephemeral_private_key, _) = Secp256k1::random_keypair();
+//!
+//! let (stealth_public_key, _) = Secp256k1::generate_stealth_address(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//! );
+//! let stealth_address = address_of(&stealth_public_key);
+//! ```
+pub(crate) mod abi;
+pub mod announcer;
+pub mod ecdsa;
+pub mod registry;
+pub mod smart_account;
+pub mod wallet_keys;
+
+use crate::prelude::PublicKey;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use crate::Secp256k1;
+
+/// An Ethereum address.
+pub type Address = [u8; 20];
+
+/// The address of a secp256k1 public key: the last 20 bytes of the Keccak-256 hash of its
+/// uncompressed coordinates.
+pub fn address_of(public_key: &PublicKey<Secp256k1>) -> Address {
+    let uncompressed = Secp256k1::encode_point_uncompressed(public_key);
+    abi::keccak256(&uncompressed[1..])[12..].try_into().unwrap()
+}
+
+/// Writes an address as 0x-prefixed hex with its EIP-55 checksum, in which the letters whose
+/// nibble of the Keccak-256 hash of the lowercase hex is at least 8 are uppercase.
+pub fn checksum_address(address: &Address) -> String {
+    let lowercase = hex::encode(address);
+    let hash = abi::keccak256(lowercase.as_bytes());
+    let digits = lowercase.chars().enumerate().map(|(index, digit)| {
+        let nibble = (hash[index / 2] >> (4 * (1 - index % 2))) & 0x0f;
+        if nibble >= 8 {
+            digit.to_ascii_uppercase()
+        } else {
+            digit
+        }
+    });
+    format!("0x{}", digits.collect::<String>())
+}
+
+/// The ERC-5564 scheme id of secp256k1, as an ABI word.
+pub(crate) fn scheme_id() -> abi::Word {
+    abi::uint(Secp256k1::SCHEME_ID.expect("secp256k1 has a scheme id"))
+}
+
+/// Parses a 0x-prefixed hex address, with or without its EIP-55 checksum.
+pub(crate) fn parse_address(text: &str) -> Option<Address> {
+    parse_hex(text)?.try_into().ok()
+}
+
+pub(crate) fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    hex::decode(text.strip_prefix("0x")?).ok()
+}
+
+/// Parses a 0x-prefixed hex quantity, as JSON-RPC writes block numbers and indices.
+pub(crate) fn parse_quantity(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode;
+    use crate::prelude::Fr;
+
+    #[test]
+    fn derives_addresses_as_ethereum_does() {
+        // the well-known account of the private key 1
+        let mut key = [0; 32];
+        key[0] = 1;
+        let public_key = Secp256k1::derive_public_key(&decode::<Fr<Secp256k1>>(&key).unwrap());
+        assert_eq!(
+            hex::encode(address_of(&public_key)),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+    }
+
+    #[test]
+    fn checksums_addresses_as_eip_55_does() {
+        // the examples of EIP-55
+        let examples = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for example in examples {
+            let address = parse_address(&example.to_lowercase()).unwrap();
+            assert_eq!(checksum_address(&address), example);
+        }
+    }
+
+    #[test]
+    fn parses_json_rpc_values() {
+        assert_eq!(parse_quantity("0x1b4"), Some(436));
+        assert_eq!(parse_quantity("1b4"), None);
+        assert_eq!(
+            parse_address("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"),
+            Some(
+                hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap()[..]
+                    .try_into()
+                    .unwrap()
+            )
+        );
+        assert_eq!(parse_address("0x7e5f"), None);
+    }
+}
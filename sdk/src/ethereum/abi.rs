@@ -0,0 +1,129 @@
+//! Just enough of the Solidity ABI for the calls and events of the stealth address contracts,
+//! whose dynamic parameters all come after their static ones.
+use super::Address;
+use tiny_keccak::{Hasher, Keccak};
+
+pub(crate) const WORD_SIZE: usize = 32;
+
+pub(crate) type Word = [u8; WORD_SIZE];
+
+pub(crate) fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// The selector of a function, from its signature, e.g. `transfer(address,uint256)`.
+pub(crate) fn selector(signature: &str) -> [u8; 4] {
+    keccak256(signature.as_bytes())[..4].try_into().unwrap()
+}
+
+pub(crate) fn uint(value: u64) -> Word {
+    let mut word = [0; WORD_SIZE];
+    word[WORD_SIZE - 8..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+pub(crate) fn address(address: &Address) -> Word {
+    let mut word = [0; WORD_SIZE];
+    word[WORD_SIZE - address.len()..].copy_from_slice(address);
+    word
+}
+
+/// Reads a `uint256` that fits in a `u64`.
+pub(crate) fn read_uint(word: &[u8]) -> Option<u64> {
+    if word.len() != WORD_SIZE {
+        return None;
+    }
+    let (high, low) = word.split_at(WORD_SIZE - 8);
+    if high.iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(low.try_into().unwrap()))
+}
+
+pub(crate) fn read_address(word: &[u8]) -> Option<Address> {
+    if word.len() != WORD_SIZE {
+        return None;
+    }
+    let (high, low) = word.split_at(WORD_SIZE - 20);
+    if high.iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(low.try_into().unwrap())
+}
+
+/// Encodes static words followed by dynamic byte strings, as the arguments of a call or the data
+/// of an event.
+pub(crate) fn encode(words: &[Word], bytes: &[&[u8]]) -> Vec<u8> {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    for word in words {
+        head.extend_from_slice(word);
+    }
+    let head_size = (words.len() + bytes.len()) * WORD_SIZE;
+    for value in bytes {
+        head.extend_from_slice(&uint((head_size + tail.len()) as u64));
+        tail.extend_from_slice(&uint(value.len() as u64));
+        tail.extend_from_slice(value);
+        tail.resize(tail.len().next_multiple_of(WORD_SIZE), 0);
+    }
+    head.extend(tail);
+    head
+}
+
+/// Encodes a call to the function with the given signature.
+pub(crate) fn encode_call(signature: &str, words: &[Word], bytes: &[&[u8]]) -> Vec<u8> {
+    let mut calldata = selector(signature).to_vec();
+    calldata.extend(encode(words, bytes));
+    calldata
+}
+
+/// Reads the byte string whose offset is in the given head word of the encoded data.
+pub(crate) fn read_bytes(data: &[u8], index: usize) -> Option<&[u8]> {
+    let word = |at: usize| data.get(at..at.checked_add(WORD_SIZE)?);
+    let offset = usize::try_from(read_uint(word(index * WORD_SIZE)?)?).ok()?;
+    let length = usize::try_from(read_uint(word(offset)?)?).ok()?;
+    let start = offset + WORD_SIZE;
+    data.get(start..start.checked_add(length)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_dynamic_parameters_after_static_ones() {
+        // `f(uint256,bytes)` with 1 and "abc", after the example in the Solidity documentation
+        let encoded = encode(&[uint(1)], &[b"abc"]);
+        let expected = hex::decode(
+            "0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000040\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             6162630000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert_eq!(encoded, expected);
+        assert_eq!(read_bytes(&encoded, 1), Some(&b"abc"[..]));
+        assert_eq!(read_uint(&encoded[..WORD_SIZE]), Some(1));
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_offsets() {
+        let mut encoded = encode(&[], &[b"abc"]);
+        encoded[WORD_SIZE - 1] = 0xff;
+        assert_eq!(read_bytes(&encoded, 0), None);
+        assert_eq!(read_bytes(&encoded[..WORD_SIZE], 0), None);
+        assert_eq!(read_bytes(&[], 0), None);
+    }
+
+    #[test]
+    fn hashes_selectors() {
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+    }
+}
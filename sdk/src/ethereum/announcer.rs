@@ -0,0 +1,255 @@
+//! The ERC-5564 announcer contract: calldata for announcing payments, and its `Announcement`
+//! events, decoded from the log objects of `eth_getLogs`, for scanning. Stealth addresses and
+//! view tags are derived as scheme 1 specifies, as `Secp256k1` derives them.
+//!
+//! ```
+//! use stealth_address_kit::ethereum::address_of;
+//! use stealth_address_kit::ethereum::announcer::{announce_calldata, Log, LoggedAnnouncement};
+//! use stealth_address_kit::prelude::*;
+//!
+//! let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+//! let (viewing_key, viewing_public_key) = Secp256k1::random_keypair();
+//! let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+//! let (stealth_public_key, metadata) = Secp256k1::generate_stealth_address_with_metadata(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//!     None,
+//! );
+//!
+//! // the sender sends the funds to the stealth address, and calls the announcer with
+//! let calldata = announce_calldata(
+//!     &address_of(&stealth_public_key),
+//!     &ephemeral_public_key,
+//!     &metadata,
+//! );
+//!
+//! // the recipient fetches the logs of the announcer, and checks each of them with
+//! fn received(log: &Log, viewing_key: Fr<Secp256k1>, spending_key: Fr<Secp256k1>) -> bool {
+//!     LoggedAnnouncement::from_log(log).is_ok_and(|announcement| {
+//!         announcement
+//!             .stealth_private_key(viewing_key, spending_key)
+//!             .is_some()
+//!     })
+//! }
+//! ```
+use super::{abi, address_of, parse_address, parse_hex, parse_quantity, scheme_id, Address};
+use crate::metadata::AnnouncementMetadata;
+use crate::prelude::{Fr, PublicKey};
+use crate::stealth_addresses::StealthAddressOnCurve;
+use crate::Secp256k1;
+use std::fmt::{Display, Formatter};
+
+/// The address of the announcer, which is deployed at the same address on every chain.
+pub const ANNOUNCER_ADDRESS: Address = [
+    0x55, 0x64, 0x9e, 0x01, 0xb5, 0xdf, 0x19, 0x8d, 0x18, 0xd9, 0x5b, 0x5c, 0xc5, 0x05, 0x16, 0x30,
+    0xcf, 0xd4, 0x55, 0x64,
+];
+
+/// The signature of the function that announces a payment.
+pub const ANNOUNCE_SIGNATURE: &str = "announce(uint256,address,bytes,bytes)";
+
+/// The signature of the event, the hash of which is the first topic of its logs.
+pub const ANNOUNCEMENT_EVENT_SIGNATURE: &str = "Announcement(uint256,address,address,bytes,bytes)";
+
+/// The topic that identifies announcement logs, e.g. to filter `eth_getLogs` by.
+pub fn announcement_topic() -> [u8; 32] {
+    abi::keccak256(ANNOUNCEMENT_EVENT_SIGNATURE.as_bytes())
+}
+
+/// Builds the calldata of `announce`, under the scheme id of secp256k1.
+pub fn announce_calldata(
+    stealth_address: &Address,
+    ephemeral_public_key: &PublicKey<Secp256k1>,
+    metadata: &AnnouncementMetadata,
+) -> Vec<u8> {
+    abi::encode_call(
+        ANNOUNCE_SIGNATURE,
+        &[scheme_id(), abi::address(stealth_address)],
+        &[
+            &Secp256k1::encode_point(ephemeral_public_key),
+            &metadata.encode(),
+        ],
+    )
+}
+
+/// A log object, as `eth_getLogs` returns them. Its other fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    /// `None` for pending logs.
+    #[serde(default)]
+    pub block_number: Option<String>,
+    #[serde(default)]
+    pub transaction_hash: Option<String>,
+    #[serde(default)]
+    pub log_index: Option<String>,
+    /// Whether the log was dropped in a reorganization.
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// Why a log could not be decoded as an announcement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogError {
+    /// The log is not of the `Announcement` event.
+    NotAnAnnouncement,
+    /// The announcement is for another scheme than secp256k1.
+    UnsupportedScheme,
+    /// The log was dropped in a reorganization.
+    Removed,
+    /// A field of the log is not valid, e.g. hex that does not parse.
+    Malformed(&'static str),
+}
+
+impl Display for LogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogError::NotAnAnnouncement => write!(f, "not an announcement log"),
+            LogError::UnsupportedScheme => write!(f, "announcement for an unsupported scheme"),
+            LogError::Removed => write!(f, "the log was removed in a reorganization"),
+            LogError::Malformed(field) => write!(f, "malformed `{field}` in the log"),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
+
+/// An announcement, as the announcer logged it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedAnnouncement {
+    /// The contract that emitted the log.
+    pub announcer: Address,
+    pub stealth_address: Address,
+    /// Who called the announcer, usually the sender.
+    pub caller: Address,
+    pub ephemeral_public_key: PublicKey<Secp256k1>,
+    pub metadata: AnnouncementMetadata,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<[u8; 32]>,
+    pub log_index: Option<u64>,
+}
+
+impl LoggedAnnouncement {
+    /// Decodes a log of the `Announcement` event, for the secp256k1 scheme.
+    pub fn from_log(log: &Log) -> Result<Self, LogError> {
+        let topics = log
+            .topics
+            .iter()
+            .map(|topic| parse_hex(topic).ok_or(LogError::Malformed("topics")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [event, scheme, stealth_address, caller] = topics.as_slice() else {
+            return Err(LogError::NotAnAnnouncement);
+        };
+        if *event != announcement_topic() {
+            return Err(LogError::NotAnAnnouncement);
+        }
+        if *scheme != scheme_id() {
+            return Err(LogError::UnsupportedScheme);
+        }
+        if log.removed {
+            return Err(LogError::Removed);
+        }
+
+        let data = parse_hex(&log.data).ok_or(LogError::Malformed("data"))?;
+        let ephemeral_public_key = abi::read_bytes(&data, 0)
+            .and_then(|bytes| Secp256k1::decode_point(bytes).ok())
+            .ok_or(LogError::Malformed("ephemeralPubKey"))?;
+        let metadata = abi::read_bytes(&data, 1)
+            .and_then(|bytes| AnnouncementMetadata::decode(bytes).ok())
+            .ok_or(LogError::Malformed("metadata"))?;
+        let optional = |field: &Option<String>, name, parse: fn(&str) -> Option<_>| {
+            field
+                .as_deref()
+                .map(|text| parse(text).ok_or(LogError::Malformed(name)))
+                .transpose()
+        };
+
+        Ok(LoggedAnnouncement {
+            announcer: parse_address(&log.address).ok_or(LogError::Malformed("address"))?,
+            stealth_address: abi::read_address(stealth_address)
+                .ok_or(LogError::Malformed("stealthAddress"))?,
+            caller: abi::read_address(caller).ok_or(LogError::Malformed("caller"))?,
+            ephemeral_public_key,
+            metadata,
+            block_number: optional(&log.block_number, "blockNumber", parse_quantity)?,
+            transaction_hash: log
+                .transaction_hash
+                .as_deref()
+                .map(|hash| {
+                    parse_hex(hash)
+                        .and_then(|hash| hash.try_into().ok())
+                        .ok_or(LogError::Malformed("transactionHash"))
+                })
+                .transpose()?,
+            log_index: optional(&log.log_index, "logIndex", parse_quantity)?,
+        })
+    }
+
+    /// Returns the stealth private key, if the announcement is addressed to the keys, i.e. the
+    /// view tag byte in its metadata matches, and its stealth address is the one the keys derive.
+    pub fn stealth_private_key(
+        &self,
+        viewing_key: Fr<Secp256k1>,
+        spending_key: Fr<Secp256k1>,
+    ) -> Option<Fr<Secp256k1>> {
+        let q_receiver = Secp256k1::compute_shared_point(viewing_key, self.ephemeral_public_key);
+        if !self
+            .metadata
+            .matches_view_tag(Secp256k1::metadata_view_tag(&q_receiver))
+        {
+            return None;
+        }
+        let stealth_private_key = spending_key + Secp256k1::hash_shared_point(&q_receiver);
+        (address_of(&Secp256k1::derive_public_key(&stealth_private_key)) == self.stealth_address)
+            .then_some(stealth_private_key)
+    }
+}
+
+/// Scans logs for the announcements addressed to the keys, skipping the logs that are not
+/// announcements for secp256k1, or were removed.
+///
+/// Returns every matching announcement, along with its stealth private key.
+pub fn scan_logs(
+    logs: &[Log],
+    viewing_key: Fr<Secp256k1>,
+    spending_key: Fr<Secp256k1>,
+) -> Vec<(LoggedAnnouncement, Fr<Secp256k1>)> {
+    logs.iter()
+        .filter_map(|log| LoggedAnnouncement::from_log(log).ok())
+        .filter_map(|announcement| {
+            let stealth_private_key =
+                announcement.stealth_private_key(viewing_key, spending_key)?;
+            Some((announcement, stealth_private_key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::Transfer;
+
+    #[test]
+    fn encodes_announce_calls() {
+        let (_, ephemeral_public_key) = Secp256k1::random_keypair();
+        let metadata =
+            AnnouncementMetadata::new(0xab, Some(Transfer::Ether { amount: [0x01; 32] }));
+        let calldata = announce_calldata(&[0x42; 20], &ephemeral_public_key, &metadata);
+
+        assert_eq!(calldata[..4], abi::selector(ANNOUNCE_SIGNATURE));
+        let arguments = &calldata[4..];
+        assert_eq!(abi::read_uint(&arguments[..32]), Some(1));
+        assert_eq!(abi::read_address(&arguments[32..64]), Some([0x42; 20]));
+        assert_eq!(
+            abi::read_bytes(arguments, 2),
+            Some(&Secp256k1::encode_point(&ephemeral_public_key)[..])
+        );
+        assert_eq!(abi::read_bytes(arguments, 3), Some(&metadata.encode()[..]));
+        // four head words, and two length words each followed by a padded payload
+        assert_eq!(arguments.len(), 32 * (4 + 1 + 2 + 1 + 2));
+    }
+}
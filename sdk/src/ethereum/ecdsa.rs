@@ -0,0 +1,273 @@
+//! Recoverable secp256k1 ECDSA signatures, as Ethereum makes and checks them: deterministic
+//! nonces after RFC 6979, `s` in the lower half of the order after EIP-2, and the parity of the
+//! nonce point in `v`, so that `ecrecover` can recover the signer.
+use super::{address_of, Address};
+use crate::prelude::{Fr, PublicKey};
+use crate::sec1;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use crate::Secp256k1;
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt::{Display, Formatter};
+
+/// The size of a signature, as `r`, `s` and `v`.
+pub const SIGNATURE_SIZE: usize = 65;
+
+/// A signature that is malformed, or from which no public key can be recovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSignature;
+
+impl Display for InvalidSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not a valid secp256k1 signature")
+    }
+}
+
+impl std::error::Error for InvalidSignature {}
+
+/// A recoverable signature, with `r` and `s` big-endian, and `v` 27 or 28.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    pub v: u8,
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        let mut bytes = [0; SIGNATURE_SIZE];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.v;
+        bytes
+    }
+
+    /// Whether `r` and `s` are reduced and not zero, and `s` is in the lower half of the order.
+    pub fn is_canonical(&self) -> bool {
+        read_scalar(&self.r).is_some() && read_scalar(&self.s).is_some_and(|s| !is_high(&s))
+    }
+
+    /// The same signature with `s` in the lower half of the order: a signature whose `s` is in
+    /// the upper half has it negated and `v` flipped, which recovers the same key.
+    pub fn normalized(&self) -> Self {
+        match read_scalar(&self.s).filter(is_high) {
+            Some(s) => Signature {
+                r: self.r,
+                s: to_bytes(&-s),
+                v: if self.v == 27 { 28 } else { 27 },
+            },
+            None => *self,
+        }
+    }
+
+    /// Reads a signature as `r`, `s` and `v`, accepting 0 and 1 for `v` as well as 27 and 28.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidSignature> {
+        let bytes: &[u8; SIGNATURE_SIZE] = bytes.try_into().map_err(|_| InvalidSignature)?;
+        let v = match bytes[64] {
+            v @ (0 | 1) => v + 27,
+            v @ (27 | 28) => v,
+            _ => return Err(InvalidSignature),
+        };
+        Ok(Signature {
+            r: bytes[..32].try_into().unwrap(),
+            s: bytes[32..64].try_into().unwrap(),
+            v,
+        })
+    }
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn to_bytes(scalar: &Fr<Secp256k1>) -> [u8; 32] {
+    scalar.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Reads a big-endian scalar, rejecting zero and values that are not reduced.
+fn read_scalar(bytes: &[u8; 32]) -> Option<Fr<Secp256k1>> {
+    let scalar = Fr::<Secp256k1>::from_be_bytes_mod_order(bytes);
+    (!scalar.is_zero() && to_bytes(&scalar) == *bytes).then_some(scalar)
+}
+
+fn is_high(scalar: &Fr<Secp256k1>) -> bool {
+    scalar.into_bigint() > Fr::<Secp256k1>::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+/// The nonces of RFC 6979 for the key and hash, with HMAC-SHA256, in the order they are tried.
+fn nonces(private_key: &Fr<Secp256k1>, hash: &[u8; 32]) -> impl Iterator<Item = Fr<Secp256k1>> {
+    let key = to_bytes(private_key);
+    let hash = to_bytes(&Fr::<Secp256k1>::from_be_bytes_mod_order(hash));
+    let mut k = [0u8; 32];
+    let mut v = [1u8; 32];
+    k = hmac(&k, &[&v, &[0], &key, &hash]);
+    v = hmac(&k, &[&v]);
+    k = hmac(&k, &[&v, &[1], &key, &hash]);
+    v = hmac(&k, &[&v]);
+
+    let mut first = true;
+    std::iter::from_fn(move || loop {
+        if !first {
+            k = hmac(&k, &[&v, &[0]]);
+            v = hmac(&k, &[&v]);
+        }
+        first = false;
+        v = hmac(&k, &[&v]);
+        if let Some(nonce) = read_scalar(&v) {
+            return Some(nonce);
+        }
+    })
+}
+
+/// Signs a 32-byte hash, e.g. an EIP-712 or EIP-191 digest.
+pub fn sign_hash(private_key: &Fr<Secp256k1>, hash: &[u8; 32]) -> Signature {
+    let e = Fr::<Secp256k1>::from_be_bytes_mod_order(hash);
+    for nonce in nonces(private_key, hash) {
+        let point = Secp256k1::derive_public_key(&nonce).into_affine();
+        let (x, y) = point.xy().expect("the nonce is not zero");
+        let x = x.into_bigint();
+        // r is x reduced by the order, which would lose the x that v cannot tell apart
+        if x >= Fr::<Secp256k1>::MODULUS {
+            continue;
+        }
+        let r = Fr::<Secp256k1>::from_bigint(x).expect("x is below the order");
+        let mut s = nonce.inverse().expect("the nonce is not zero") * (e + r * private_key);
+        if r.is_zero() || s.is_zero() {
+            continue;
+        }
+        let mut is_odd = y.into_bigint().is_odd();
+        if is_high(&s) {
+            s = -s;
+            is_odd = !is_odd;
+        }
+        return Signature {
+            r: to_bytes(&r),
+            s: to_bytes(&s),
+            v: 27 + u8::from(is_odd),
+        };
+    }
+    unreachable!("the nonces of RFC 6979 do not run out")
+}
+
+/// Recovers the public key that signed the hash, as `ecrecover` does, but rejecting signatures
+/// whose `s` is in the upper half of the order, as EIP-2 makes them malleable.
+pub fn recover(
+    hash: &[u8; 32],
+    signature: &Signature,
+) -> Result<PublicKey<Secp256k1>, InvalidSignature> {
+    let r = read_scalar(&signature.r).ok_or(InvalidSignature)?;
+    let s = read_scalar(&signature.s).ok_or(InvalidSignature)?;
+    if is_high(&s) {
+        return Err(InvalidSignature);
+    }
+    let prefix = match signature.v {
+        27 => 0x02,
+        28 => 0x03,
+        _ => return Err(InvalidSignature),
+    };
+    let point =
+        sec1::decode::<ark_secp256k1::Config>(&[&[prefix], &signature.r[..]].concat(), true)
+            .map_err(|_| InvalidSignature)?;
+
+    let e = Fr::<Secp256k1>::from_be_bytes_mod_order(hash);
+    let r_inverse = r.inverse().expect("r is not zero");
+    let public_key = (point * s - PublicKey::<Secp256k1>::generator() * e) * r_inverse;
+    if public_key.is_zero() {
+        return Err(InvalidSignature);
+    }
+    Ok(public_key)
+}
+
+/// Recovers the address of the signer of the hash.
+pub fn recover_address(
+    hash: &[u8; 32],
+    signature: &Signature,
+) -> Result<Address, InvalidSignature> {
+    recover(hash, signature).map(|public_key| address_of(&public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::abi::keccak256;
+    use k256::ecdsa::SigningKey;
+
+    fn key(seed: u8) -> Fr<Secp256k1> {
+        Secp256k1::hash_to_fr(&[seed])
+    }
+
+    fn other_parity(v: u8) -> u8 {
+        if v == 27 {
+            28
+        } else {
+            27
+        }
+    }
+
+    #[test]
+    fn signs_as_k256_does() {
+        for seed in 0..16 {
+            let private_key = key(seed);
+            let hash = keccak256(&[seed, 0xab]);
+            let signature = sign_hash(&private_key, &hash);
+
+            let signing_key = SigningKey::from_slice(&to_bytes(&private_key)).unwrap();
+            let (expected, recovery_id) = signing_key.sign_prehash_recoverable(&hash).unwrap();
+            assert_eq!(signature.r[..], expected.r().to_bytes()[..]);
+            assert_eq!(signature.s[..], expected.s().to_bytes()[..]);
+            assert_eq!(signature.v, 27 + recovery_id.to_byte());
+        }
+    }
+
+    #[test]
+    fn recovers_the_signer() {
+        let private_key = key(1);
+        let hash = keccak256(b"message");
+        let signature = sign_hash(&private_key, &hash);
+        let public_key = Secp256k1::derive_public_key(&private_key);
+
+        assert_eq!(recover(&hash, &signature), Ok(public_key));
+        assert_eq!(Signature::from_bytes(&signature.to_bytes()), Ok(signature));
+        assert_ne!(recover(&keccak256(b"other"), &signature), Ok(public_key));
+        let mut flipped = signature;
+        flipped.v = other_parity(flipped.v);
+        assert_ne!(recover(&hash, &flipped), Ok(public_key));
+    }
+
+    #[test]
+    fn rejects_malleable_and_malformed_signatures() {
+        let hash = keccak256(b"message");
+        let signature = sign_hash(&key(2), &hash);
+
+        // the same signature with s negated, which ecrecover would accept too
+        let s = read_scalar(&signature.s).unwrap();
+        let malleable = Signature {
+            s: to_bytes(&-s),
+            v: other_parity(signature.v),
+            ..signature
+        };
+        assert_eq!(recover(&hash, &malleable), Err(InvalidSignature));
+
+        let zero_r = Signature {
+            r: [0; 32],
+            ..signature
+        };
+        assert_eq!(recover(&hash, &zero_r), Err(InvalidSignature));
+        let bytes = signature.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes[..64]), Err(InvalidSignature));
+        let mut bad_v = bytes;
+        bad_v[64] = 29;
+        assert_eq!(Signature::from_bytes(&bad_v), Err(InvalidSignature));
+        let mut zero_v = bytes;
+        zero_v[64] -= 27;
+        assert_eq!(Signature::from_bytes(&zero_v), Ok(signature));
+    }
+}
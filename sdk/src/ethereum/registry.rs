@@ -0,0 +1,309 @@
+//! The ERC-6538 registry, through which recipients publish their meta-addresses: calldata for
+//! `registerKeys`, and the EIP-712 signatures with which a relayer can `registerKeysOnBehalf` of
+//! a registrant.
+//!
+//! ```
+//! use stealth_address_kit::ethereum::address_of;
+//! use stealth_address_kit::ethereum::registry::{decode_nonce, nonce_of_calldata, Registration};
+//! use stealth_address_kit::prelude::*;
+//!
+//! let (registrant_key, registrant_public_key) = Secp256k1::random_keypair();
+//! let registrant = address_of(&registrant_public_key);
+//! let (_, spending_public_key) = Secp256k1::random_keypair();
+//! let (_, viewing_public_key) = Secp256k1::random_keypair();
+//! let meta_address = MetaAddress::<Secp256k1>::new(spending_public_key, viewing_public_key);
+//!
+//! // the relayer reads the nonce of the registrant with an `eth_call` of
+//! let calldata = nonce_of_calldata(&registrant);
+//! # let returned = [0u8; 32];
+//! let nonce = decode_nonce(&returned).unwrap();
+//!
+//! // the registrant signs the registration
+//! let registration = Registration::new(1, meta_address, nonce);
+//! let signature = registration.sign(&registrant_key);
+//!
+//! // and the relayer checks the signature, and submits it
+//! assert!(registration.verify(&registrant, &signature));
+//! let calldata = registration.register_keys_on_behalf_calldata(&registrant, &signature);
+//! ```
+use super::ecdsa::{recover_address, sign_hash, Signature};
+use super::{abi, scheme_id, Address};
+use crate::meta_address::MetaAddress;
+use crate::prelude::Fr;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use crate::Secp256k1;
+
+/// The address of the registry, which is deployed at the same address on every chain.
+pub const REGISTRY_ADDRESS: Address = [
+    0x65, 0x38, 0xe6, 0xbf, 0x4b, 0x0e, 0xbd, 0x30, 0xa8, 0xea, 0x09, 0x30, 0x27, 0xac, 0x24, 0x22,
+    0xce, 0x5d, 0x65, 0x38,
+];
+
+pub const REGISTER_KEYS_SIGNATURE: &str = "registerKeys(uint256,bytes)";
+pub const REGISTER_KEYS_ON_BEHALF_SIGNATURE: &str =
+    "registerKeysOnBehalf(address,uint256,bytes,bytes)";
+pub const NONCE_OF_SIGNATURE: &str = "nonceOf(address)";
+pub const INCREMENT_NONCE_SIGNATURE: &str = "incrementNonce()";
+
+/// The name and version of the EIP-712 domain of the registry.
+pub const DOMAIN_NAME: &str = "ERC6538Registry";
+pub const DOMAIN_VERSION: &str = "1.0";
+
+const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+/// The EIP-712 type of the registrations a registrant signs.
+pub const ENTRY_TYPE: &str =
+    "Erc6538RegistryEntry(uint256 schemeId,bytes stealthMetaAddress,uint256 nonce)";
+
+/// The stealth meta-address, as the registry stores it for the secp256k1 scheme: the compressed
+/// spending public key followed by the compressed viewing public key.
+pub fn stealth_meta_address_bytes(meta_address: &MetaAddress<Secp256k1>) -> Vec<u8> {
+    [
+        Secp256k1::encode_point(&meta_address.spending_public_key),
+        Secp256k1::encode_point(&meta_address.viewing_public_key),
+    ]
+    .concat()
+}
+
+/// Builds the calldata of `registerKeys`, with which the sender registers their own
+/// meta-address.
+pub fn register_keys_calldata(meta_address: &MetaAddress<Secp256k1>) -> Vec<u8> {
+    abi::encode_call(
+        REGISTER_KEYS_SIGNATURE,
+        &[scheme_id()],
+        &[&stealth_meta_address_bytes(meta_address)],
+    )
+}
+
+/// Builds the calldata of `nonceOf`, which returns the nonce the next registration of the
+/// registrant must be signed with.
+pub fn nonce_of_calldata(registrant: &Address) -> Vec<u8> {
+    abi::encode_call(NONCE_OF_SIGNATURE, &[abi::address(registrant)], &[])
+}
+
+/// Decodes what `nonceOf` returns.
+pub fn decode_nonce(returned: &[u8]) -> Option<u64> {
+    abi::read_uint(returned)
+}
+
+/// Builds the calldata of `incrementNonce`, with which a registrant invalidates the
+/// registrations they signed but that were not submitted yet.
+pub fn increment_nonce_calldata() -> Vec<u8> {
+    abi::encode_call(INCREMENT_NONCE_SIGNATURE, &[], &[])
+}
+
+/// The separator of an EIP-712 domain with a name, a version, a chain id and a contract.
+pub(crate) fn domain_separator(
+    name: &str,
+    version: &str,
+    chain_id: u64,
+    verifying_contract: &Address,
+) -> [u8; 32] {
+    abi::keccak256(&abi::encode(
+        &[
+            abi::keccak256(DOMAIN_TYPE.as_bytes()),
+            abi::keccak256(name.as_bytes()),
+            abi::keccak256(version.as_bytes()),
+            abi::uint(chain_id),
+            abi::address(verifying_contract),
+        ],
+        &[],
+    ))
+}
+
+/// The EIP-712 digest of a struct in a domain, which is what gets signed.
+pub(crate) fn typed_data_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    abi::keccak256(&[&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat())
+}
+
+/// A registration of a meta-address, for a registrant to sign so that anyone can submit it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registration {
+    pub chain_id: u64,
+    /// The registry, which the signature is only valid for.
+    pub registry: Address,
+    pub meta_address: MetaAddress<Secp256k1>,
+    /// The nonce of the registrant in the registry, as `nonceOf` returns it.
+    pub nonce: u64,
+}
+
+impl Registration {
+    /// A registration with the registry deployed at `REGISTRY_ADDRESS`.
+    pub fn new(chain_id: u64, meta_address: MetaAddress<Secp256k1>, nonce: u64) -> Self {
+        Registration {
+            chain_id,
+            registry: REGISTRY_ADDRESS,
+            meta_address,
+            nonce,
+        }
+    }
+
+    pub fn domain_separator(&self) -> [u8; 32] {
+        domain_separator(DOMAIN_NAME, DOMAIN_VERSION, self.chain_id, &self.registry)
+    }
+
+    pub fn struct_hash(&self) -> [u8; 32] {
+        let meta_address = stealth_meta_address_bytes(&self.meta_address);
+        abi::keccak256(&abi::encode(
+            &[
+                abi::keccak256(ENTRY_TYPE.as_bytes()),
+                scheme_id(),
+                abi::keccak256(&meta_address),
+                abi::uint(self.nonce),
+            ],
+            &[],
+        ))
+    }
+
+    /// The EIP-712 digest the registrant signs.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        typed_data_hash(&self.domain_separator(), &self.struct_hash())
+    }
+
+    /// Signs the registration with the key of the registrant.
+    pub fn sign(&self, registrant_key: &Fr<Secp256k1>) -> Signature {
+        sign_hash(registrant_key, &self.signing_hash())
+    }
+
+    /// Whether the signature is by the registrant, as the registry checks it for accounts that
+    /// are not contracts.
+    pub fn verify(&self, registrant: &Address, signature: &Signature) -> bool {
+        recover_address(&self.signing_hash(), signature).is_ok_and(|signer| signer == *registrant)
+    }
+
+    /// Builds the calldata of `registerKeysOnBehalf`, for a relayer to submit.
+    pub fn register_keys_on_behalf_calldata(
+        &self,
+        registrant: &Address,
+        signature: &Signature,
+    ) -> Vec<u8> {
+        abi::encode_call(
+            REGISTER_KEYS_ON_BEHALF_SIGNATURE,
+            &[abi::address(registrant), scheme_id()],
+            &[
+                &signature.to_bytes(),
+                &stealth_meta_address_bytes(&self.meta_address),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::address_of;
+
+    fn hash_string(value: &str) -> abi::Word {
+        abi::keccak256(value.as_bytes())
+    }
+
+    #[test]
+    fn hashes_typed_data_as_eip_712_does() {
+        // the example of EIP-712, a mail from Cow to Bob
+        let person_type = "Person(string name,address wallet)";
+        let mail_type = format!("Mail(Person from,Person to,string contents){person_type}");
+        let person = |name: &str, wallet: &str| {
+            let wallet: Address = hex::decode(wallet).unwrap().try_into().unwrap();
+            abi::keccak256(&abi::encode(
+                &[
+                    hash_string(person_type),
+                    hash_string(name),
+                    abi::address(&wallet),
+                ],
+                &[],
+            ))
+        };
+        let mail = abi::keccak256(&abi::encode(
+            &[
+                hash_string(&mail_type),
+                person("Cow", "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"),
+                person("Bob", "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+                hash_string("Hello, Bob!"),
+            ],
+            &[],
+        ));
+        let domain = domain_separator("Ether Mail", "1", 1, &[0xcc; 20]);
+
+        assert_eq!(
+            hex::encode(domain),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(typed_data_hash(&domain, &mail)),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    fn registration() -> (Fr<Secp256k1>, Address, Registration) {
+        let registrant_key = Secp256k1::hash_to_fr(b"registrant");
+        let registrant = address_of(&Secp256k1::derive_public_key(&registrant_key));
+        let meta_address = MetaAddress::new(
+            Secp256k1::derive_public_key(&Secp256k1::hash_to_fr(b"spending")),
+            Secp256k1::derive_public_key(&Secp256k1::hash_to_fr(b"viewing")),
+        );
+        (
+            registrant_key,
+            registrant,
+            Registration::new(1, meta_address, 0),
+        )
+    }
+
+    #[test]
+    fn signatures_are_bound_to_the_registration() {
+        let (registrant_key, registrant, registration) = registration();
+        let signature = registration.sign(&registrant_key);
+        assert!(registration.verify(&registrant, &signature));
+
+        // a signature for one nonce, chain or registry cannot be replayed for another
+        let replays = [
+            Registration {
+                nonce: 1,
+                ..registration
+            },
+            Registration {
+                chain_id: 10,
+                ..registration
+            },
+            Registration {
+                registry: [0; 20],
+                ..registration
+            },
+        ];
+        for replay in replays {
+            assert!(!replay.verify(&registrant, &signature));
+        }
+        assert!(!registration.verify(&[0; 20], &signature));
+    }
+
+    #[test]
+    fn encodes_registry_calls() {
+        let (registrant_key, registrant, registration) = registration();
+        let meta_address = stealth_meta_address_bytes(&registration.meta_address);
+        assert_eq!(meta_address.len(), 66);
+
+        let calldata = register_keys_calldata(&registration.meta_address);
+        assert_eq!(calldata[..4], abi::selector(REGISTER_KEYS_SIGNATURE));
+        assert_eq!(abi::read_uint(&calldata[4..36]), Some(1));
+        assert_eq!(abi::read_bytes(&calldata[4..], 1), Some(&meta_address[..]));
+
+        let signature = registration.sign(&registrant_key);
+        let calldata = registration.register_keys_on_behalf_calldata(&registrant, &signature);
+        let arguments = &calldata[4..];
+        assert_eq!(abi::read_address(&arguments[..32]), Some(registrant));
+        assert_eq!(abi::read_uint(&arguments[32..64]), Some(1));
+        assert_eq!(
+            abi::read_bytes(arguments, 2),
+            Some(&signature.to_bytes()[..])
+        );
+        assert_eq!(abi::read_bytes(arguments, 3), Some(&meta_address[..]));
+
+        let calldata = nonce_of_calldata(&registrant);
+        assert_eq!(calldata.len(), 4 + 32);
+        assert_eq!(abi::read_address(&calldata[4..]), Some(registrant));
+        assert_eq!(decode_nonce(&abi::uint(7)), Some(7));
+        assert_eq!(
+            increment_nonce_calldata(),
+            abi::selector(INCREMENT_NONCE_SIGNATURE)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Smart accounts as stealth destinations: the counterfactual ERC-4337 account that an account
+//! factory would deploy with CREATE2 for the owner a stealth public key controls, so that a
+//! payment can be sent to the account before it exists.
+//!
+//! The sender computes the address from the stealth public key, and the recipient computes the
+//! same address from the stealth private key, along with the `initCode` of the user operation that
+//! deploys the account. The account is then controlled by signing with the stealth private key.
+//!
+//! ```
+//! use stealth_address_kit::ethereum::smart_account::{AccountFactory, SaltDerivation};
+//! use stealth_address_kit::prelude::*;
+//!
+//! let factory = AccountFactory {
+//!     address: [0x11; 20],
+//!     init_code_hash: [0x22; 32],
+//!     salt: SaltDerivation::PackedOwnerAndIndex,
+//!     create_account_signature: "createAccount(address,uint256)",
+//! };
+//! let (viewing_key, viewing_public_key) = Secp256k1::random_keypair();
+//! let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+//! let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+//!
+//! // the sender pays into the account of the stealth public key
+//! let (stealth_public_key, view_tag) = Secp256k1::generate_stealth_address(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//! );
+//! let account = factory.stealth_account_address(&stealth_public_key, 0);
+//!
+//! // the recipient finds the same account, and deploys it with the `initCode`
+//! let stealth_private_key = Secp256k1::generate_stealth_private_key(
+//!     ephemeral_public_key,
+//!     viewing_key,
+//!     spending_key,
+//!     view_tag,
+//! )
+//! .unwrap();
+//! let stealth_public_key = Secp256k1::derive_public_key(&stealth_private_key);
+//! assert_eq!(factory.stealth_account_address(&stealth_public_key, 0), account);
+//! let init_code = factory.stealth_init_code(&stealth_public_key, 0);
+//! ```
+use super::{abi, address_of, Address};
+use crate::prelude::PublicKey;
+use crate::Secp256k1;
+
+/// The address of a contract deployed with CREATE2, after EIP-1014: the last 20 bytes of the
+/// Keccak-256 hash of `0xff`, the deployer, the salt and the hash of the creation code.
+pub fn create2_address(deployer: &Address, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Address {
+    let hash = abi::keccak256(&[&[0xff], &deployer[..], &salt[..], &init_code_hash[..]].concat());
+    hash[12..].try_into().unwrap()
+}
+
+/// How a factory derives the CREATE2 salt of an account from its owner and index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaltDerivation {
+    /// `keccak256(abi.encodePacked(owner, index))`, as LightAccount's factory derives it.
+    PackedOwnerAndIndex,
+    /// `keccak256(abi.encode(owner, index))`.
+    OwnerAndIndex,
+    /// The owner, as a word, for factories that deploy a single account per owner.
+    Owner,
+}
+
+/// An account factory that deploys the accounts of an owner with CREATE2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountFactory {
+    /// The factory, which is the deployer of the accounts.
+    pub address: Address,
+    /// The Keccak-256 hash of the creation code of the accounts, which must not depend on the
+    /// owner, as with the proxies and clones of an implementation most factories deploy.
+    pub init_code_hash: [u8; 32],
+    pub salt: SaltDerivation,
+    /// The factory function that deploys an account, which takes the owner and the index, e.g.
+    /// `createAccount(address,uint256)`.
+    pub create_account_signature: &'static str,
+}
+
+impl AccountFactory {
+    /// The salt of the account of the owner with the given index.
+    pub fn salt(&self, owner: &Address, index: u64) -> [u8; 32] {
+        match self.salt {
+            SaltDerivation::PackedOwnerAndIndex => {
+                abi::keccak256(&[&owner[..], &abi::uint(index)].concat())
+            }
+            SaltDerivation::OwnerAndIndex => {
+                abi::keccak256(&abi::encode(&[abi::address(owner), abi::uint(index)], &[]))
+            }
+            SaltDerivation::Owner => abi::address(owner),
+        }
+    }
+
+    /// The counterfactual address of the account of the owner with the given index.
+    pub fn account_address(&self, owner: &Address, index: u64) -> Address {
+        create2_address(
+            &self.address,
+            &self.salt(owner, index),
+            &self.init_code_hash,
+        )
+    }
+
+    /// The calldata of the factory call that deploys the account of the owner.
+    pub fn create_account_calldata(&self, owner: &Address, index: u64) -> Vec<u8> {
+        abi::encode_call(
+            self.create_account_signature,
+            &[abi::address(owner), abi::uint(index)],
+            &[],
+        )
+    }
+
+    /// The `initCode` of the user operation that deploys the account of the owner: the factory
+    /// followed by the calldata of its call.
+    pub fn init_code(&self, owner: &Address, index: u64) -> Vec<u8> {
+        let mut init_code = self.address.to_vec();
+        init_code.extend(self.create_account_calldata(owner, index));
+        init_code
+    }
+
+    /// The counterfactual address of the account owned by the address of a stealth public key.
+    pub fn stealth_account_address(
+        &self,
+        stealth_public_key: &PublicKey<Secp256k1>,
+        index: u64,
+    ) -> Address {
+        self.account_address(&address_of(stealth_public_key), index)
+    }
+
+    /// The `initCode` that deploys the account owned by the address of a stealth public key.
+    pub fn stealth_init_code(
+        &self,
+        stealth_public_key: &PublicKey<Secp256k1>,
+        index: u64,
+    ) -> Vec<u8> {
+        self.init_code(&address_of(stealth_public_key), index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stealth_addresses::StealthAddressOnCurve;
+
+    fn address(text: &str) -> Address {
+        hex::decode(text).unwrap().try_into().unwrap()
+    }
+
+    fn word(text: &str) -> [u8; 32] {
+        hex::decode(format!("{text:0>64}"))
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn computes_create2_addresses_as_eip_1014_does() {
+        // the examples of EIP-1014, as deployer, salt, creation code and address
+        let examples = [
+            (
+                "0000000000000000000000000000000000000000",
+                "00",
+                "00",
+                "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "00",
+                "00",
+                "b928f69bb1d91cd65274e3c79d8986362984fda3",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "cafebabe",
+                "deadbeef",
+                "60f3f640a8508fc6a86d45df051962668e1e8ac7",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "00",
+                "",
+                "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0",
+            ),
+        ];
+        for (deployer, salt, init_code, expected) in examples {
+            let init_code_hash = abi::keccak256(&hex::decode(init_code).unwrap());
+            assert_eq!(
+                hex::encode(create2_address(
+                    &address(deployer),
+                    &word(salt),
+                    &init_code_hash
+                )),
+                expected
+            );
+        }
+    }
+
+    fn factory(salt: SaltDerivation) -> AccountFactory {
+        AccountFactory {
+            address: address("00004ec70002a32400f8ae005a26081065620d20"),
+            init_code_hash: abi::keccak256(b"account"),
+            salt,
+            create_account_signature: "createAccount(address,uint256)",
+        }
+    }
+
+    #[test]
+    fn derives_salts_from_the_owner_and_index() {
+        let owner = [0xab; 20];
+        let packed = factory(SaltDerivation::PackedOwnerAndIndex).salt(&owner, 7);
+        let encoded = factory(SaltDerivation::OwnerAndIndex).salt(&owner, 7);
+
+        let mut index = [0; 32];
+        index[31] = 7;
+        assert_eq!(packed, abi::keccak256(&[&owner[..], &index].concat()));
+        assert_eq!(
+            encoded,
+            abi::keccak256(&[&[0; 12], &owner[..], &index].concat())
+        );
+        assert_eq!(factory(SaltDerivation::Owner).salt(&owner, 7)[12..], owner);
+        assert_ne!(
+            packed,
+            factory(SaltDerivation::PackedOwnerAndIndex).salt(&owner, 8)
+        );
+    }
+
+    #[test]
+    fn builds_the_init_code_of_the_account() {
+        let factory = factory(SaltDerivation::PackedOwnerAndIndex);
+        let owner = [0xab; 20];
+        let init_code = factory.init_code(&owner, 7);
+
+        assert_eq!(init_code[..20], factory.address);
+        let calldata = &init_code[20..];
+        assert_eq!(calldata[..4], [0x5f, 0xbf, 0xb9, 0xcf]);
+        assert_eq!(abi::read_address(&calldata[4..36]), Some(owner));
+        assert_eq!(abi::read_uint(&calldata[36..]), Some(7));
+    }
+
+    #[test]
+    fn the_recipient_finds_the_account_of_the_sender() {
+        let factory = factory(SaltDerivation::OwnerAndIndex);
+        let (viewing_key, viewing_public_key) = Secp256k1::random_keypair();
+        let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+        let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+
+        let (stealth_public_key, view_tag) = Secp256k1::generate_stealth_address(
+            viewing_public_key,
+            spending_public_key,
+            ephemeral_private_key,
+        );
+        let stealth_private_key = Secp256k1::generate_stealth_private_key(
+            ephemeral_public_key,
+            viewing_key,
+            spending_key,
+            view_tag,
+        )
+        .unwrap();
+        let owner = address_of(&Secp256k1::derive_public_key(&stealth_private_key));
+
+        assert_eq!(
+            factory.stealth_account_address(&stealth_public_key, 0),
+            factory.account_address(&owner, 0)
+        );
+        assert_ne!(
+            factory.stealth_account_address(&stealth_public_key, 0),
+            factory.stealth_account_address(&stealth_public_key, 1)
+        );
+        assert_eq!(
+            factory.stealth_init_code(&stealth_public_key, 0),
+            factory.init_code(&owner, 0)
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Spending and viewing keys derived from a wallet signature over a fixed message, as Umbra and
+//! the ERC-5564 stealth address SDK derive them, so that a wallet can restore them without a
+//! backup of its own. The signature is split into `r` and `s`, and each is hashed into a key:
+//! `r` into the spending key, and `s` into the viewing key.
+//!
+//! ```
+//! use stealth_address_kit::ethereum::ecdsa::sign_hash;
+//! use stealth_address_kit::ethereum::wallet_keys::{
+//!     keys_from_signature, personal_message_hash, umbra_message, KeyDerivation,
+//! };
+//! use stealth_address_kit::prelude::*;
+//!
+//! // the wallet signs the message with `personal_sign`
+//! let (wallet_key, _) = Secp256k1::random_keypair();
+//! let signature = sign_hash(&wallet_key, &personal_message_hash(umbra_message(1).as_bytes()));
+//!
+//! let (spending_key, viewing_key) =
+//!     keys_from_signature::<Secp256k1>(&signature.to_bytes(), KeyDerivation::Umbra).unwrap();
+//! ```
+use super::abi::keccak256;
+use super::ecdsa::{recover_address, InvalidSignature, Signature};
+use super::Address;
+use crate::prelude::Fr;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use ark_ff::{PrimeField, Zero};
+use sha2::{Digest, Sha256};
+
+/// The message Umbra asks wallets to sign on mainnet.
+pub const UMBRA_MESSAGE: &str =
+    "Sign this message to access your Umbra account.\n\nOnly sign this message for a trusted client!";
+
+/// The message Umbra asks wallets to sign on a chain, which names the chain but on mainnet, so
+/// that signatures cannot be replayed across chains.
+pub fn umbra_message(chain_id: u64) -> String {
+    if chain_id == 1 {
+        UMBRA_MESSAGE.to_owned()
+    } else {
+        format!("{UMBRA_MESSAGE}\n\nChain ID: {chain_id}")
+    }
+}
+
+/// The hash `personal_sign` signs for a message, after EIP-191.
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    keccak256(&[prefix.as_bytes(), message].concat())
+}
+
+/// How `r` and `s` are hashed into keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDerivation {
+    /// SHA-256, as Umbra hashes them.
+    Umbra,
+    /// Keccak-256, as the ERC-5564 stealth address SDK hashes them.
+    StealthAddressSdk,
+}
+
+impl KeyDerivation {
+    fn hash(&self, portion: &[u8; 32]) -> [u8; 32] {
+        match self {
+            KeyDerivation::Umbra => Sha256::digest(portion).into(),
+            KeyDerivation::StealthAddressSdk => keccak256(portion),
+        }
+    }
+}
+
+/// Derives the spending and viewing keys from a 65-byte signature, as `r`, `s` and `v`.
+///
+/// A signature with `s` in the upper half of the order is normalized first, as
+/// `Signature::normalized` does, so that the same wallet always restores the same keys, whether
+/// or not it normalizes its signatures. Wallets make low-`s` signatures, which are hashed as
+/// they are. The hashes are read as big-endian integers, and reduced by the order of the curve,
+/// which leaves them unchanged on secp256k1 but for a negligible fraction of them.
+pub fn keys_from_signature<C: StealthAddressOnCurve>(
+    signature: &[u8],
+    derivation: KeyDerivation,
+) -> Result<(Fr<C>, Fr<C>), InvalidSignature> {
+    let signature = Signature::from_bytes(signature)?.normalized();
+    if !signature.is_canonical() {
+        return Err(InvalidSignature);
+    }
+    let key = |portion| {
+        let key = Fr::<C>::from_be_bytes_mod_order(&derivation.hash(portion));
+        (!key.is_zero()).then_some(key).ok_or(InvalidSignature)
+    };
+    Ok((key(&signature.r)?, key(&signature.s)?))
+}
+
+/// Derives the keys as `keys_from_signature` does, after checking that the signature is the
+/// signer's `personal_sign` signature of the message.
+pub fn keys_from_signed_message<C: StealthAddressOnCurve>(
+    signature: &[u8],
+    message: &[u8],
+    signer: &Address,
+    derivation: KeyDerivation,
+) -> Result<(Fr<C>, Fr<C>), InvalidSignature> {
+    let parsed = Signature::from_bytes(signature)?.normalized();
+    if recover_address(&personal_message_hash(message), &parsed)? != *signer {
+        return Err(InvalidSignature);
+    }
+    keys_from_signature::<C>(signature, derivation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::address_of;
+    use crate::ethereum::ecdsa::sign_hash;
+    use crate::Secp256k1;
+    use ark_ff::BigInteger;
+    use k256::ecdsa::SigningKey;
+
+    /// The first development account of Hardhat and Anvil.
+    const WALLET_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const WALLET_ADDRESS: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+    fn wallet_key() -> Fr<Secp256k1> {
+        Fr::<Secp256k1>::from_be_bytes_mod_order(&hex::decode(WALLET_KEY).unwrap())
+    }
+
+    fn to_hex(key: &Fr<Secp256k1>) -> String {
+        hex::encode(key.into_bigint().to_bytes_be())
+    }
+
+    /// The signature of the Umbra mainnet message by the wallet, with `personal_sign`. The
+    /// vectors below were computed with this crate and checked against k256 and the hashes of
+    /// `r` and `s`, not taken from Umbra's own tests.
+    const SIGNATURE: &str = "c6a55b1cbd797fd4b74b66c3fa3edaea41617c04e824a92584c309ebb6a7f4e5\
+                             4c50614e0cc6df7acd83e30124c6e74380444663a3cf5f1db079fb2d67042a681c";
+
+    #[test]
+    fn signs_the_umbra_message_as_wallets_do() {
+        let hash = personal_message_hash(umbra_message(1).as_bytes());
+        let signature = sign_hash(&wallet_key(), &hash);
+        assert_eq!(hex::encode(signature.to_bytes()), SIGNATURE);
+
+        let signing_key = SigningKey::from_slice(&hex::decode(WALLET_KEY).unwrap()).unwrap();
+        let (expected, recovery_id) = signing_key.sign_prehash_recoverable(&hash).unwrap();
+        assert_eq!(signature.r[..], expected.r().to_bytes()[..]);
+        assert_eq!(signature.s[..], expected.s().to_bytes()[..]);
+        assert_eq!(signature.v, 27 + recovery_id.to_byte());
+        assert_eq!(
+            hex::encode(address_of(&Secp256k1::derive_public_key(&wallet_key()))),
+            WALLET_ADDRESS
+        );
+    }
+
+    #[test]
+    fn derives_keys_by_hashing_r_and_s() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let vectors = [
+            (
+                KeyDerivation::Umbra,
+                "2c6023a8e864c413d7df062581769a1edc295ec3db5a876a16753b112ee2384e",
+                "10b4a72f2c9e3429a42682f9e3a49bf27f35f0c365181f1ea23b72ea8e958b3d",
+            ),
+            (
+                KeyDerivation::StealthAddressSdk,
+                "2bf7d7dc1454760819b622e6e5790784972dc85b1ae66bbe19cd2ffd2d4f0f0a",
+                "f0ae561707c25c6c00c1ca63e06d76240df34c4e92810b3e2ad3d01d0381dd32",
+            ),
+        ];
+        for (derivation, spending_key, viewing_key) in vectors {
+            let keys = keys_from_signature::<Secp256k1>(&signature, derivation).unwrap();
+            assert_eq!(to_hex(&keys.0), spending_key);
+            assert_eq!(to_hex(&keys.1), viewing_key);
+        }
+        assert_eq!(hex::encode(Sha256::digest(&signature[..32])), vectors[0].1);
+        assert_eq!(hex::encode(keccak256(&signature[32..64])), vectors[1].2);
+    }
+
+    #[test]
+    fn checks_the_signer_of_the_message() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let message = umbra_message(1);
+        let wallet: Address = hex::decode(WALLET_ADDRESS).unwrap().try_into().unwrap();
+
+        assert_eq!(
+            keys_from_signed_message::<Secp256k1>(
+                &signature,
+                message.as_bytes(),
+                &wallet,
+                KeyDerivation::Umbra
+            ),
+            keys_from_signature::<Secp256k1>(&signature, KeyDerivation::Umbra)
+        );
+        // the signature is for mainnet only
+        assert_eq!(
+            keys_from_signed_message::<Secp256k1>(
+                &signature,
+                umbra_message(10).as_bytes(),
+                &wallet,
+                KeyDerivation::Umbra
+            ),
+            Err(InvalidSignature)
+        );
+        assert!(umbra_message(10).ends_with("\n\nChain ID: 10"));
+    }
+
+    #[test]
+    fn rejects_malformed_signatures() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let derive =
+            |signature: &[u8]| keys_from_signature::<Secp256k1>(signature, KeyDerivation::Umbra);
+
+        assert_eq!(derive(&signature[..64]), Err(InvalidSignature));
+        let mut zero_r = signature.clone();
+        zero_r[..32].fill(0);
+        assert_eq!(derive(&zero_r), Err(InvalidSignature));
+        let mut unreduced_s = signature.clone();
+        unreduced_s[32..64].fill(0xff);
+        assert_eq!(derive(&unreduced_s), Err(InvalidSignature));
+    }
+
+    /// The signature with `s` negated and `v` flipped, which is as valid, but not canonical.
+    const HIGH_S_SIGNATURE: &str = "c6a55b1cbd797fd4b74b66c3fa3edaea41617c04e824a92584c309ebb6a7f4e5\
+                                    b3af9eb1f3392085327c1cfedb3918bb3a6a96830b79411e0f58635f693216d91b";
+
+    #[test]
+    fn normalizes_high_s_signatures() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let high_s = hex::decode(HIGH_S_SIGNATURE).unwrap();
+        let s = Fr::<Secp256k1>::from_be_bytes_mod_order(&signature[32..64]);
+        assert_eq!(high_s[32..64], (-s).into_bigint().to_bytes_be());
+        assert_eq!(
+            Signature::from_bytes(&high_s).unwrap().normalized(),
+            Signature::from_bytes(&signature).unwrap()
+        );
+
+        let wallet: Address = hex::decode(WALLET_ADDRESS).unwrap().try_into().unwrap();
+        for derivation in [KeyDerivation::Umbra, KeyDerivation::StealthAddressSdk] {
+            let keys = keys_from_signature::<Secp256k1>(&signature, derivation);
+            assert_eq!(keys_from_signature::<Secp256k1>(&high_s, derivation), keys);
+            assert_eq!(
+                keys_from_signed_message::<Secp256k1>(
+                    &high_s,
+                    umbra_message(1).as_bytes(),
+                    &wallet,
+                    derivation
+                ),
+                keys
+            );
+        }
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn derives_keys_on_other_curves() {
+        use crate::Ed25519;
+
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let (spending_key, viewing_key) =
+            keys_from_signature::<Ed25519>(&signature, KeyDerivation::Umbra).unwrap();
+        let hash = Sha256::digest(&signature[..32]);
+        assert_eq!(spending_key, Fr::<Ed25519>::from_be_bytes_mod_order(&hash));
+        assert_ne!(spending_key, viewing_key);
+    }
+}
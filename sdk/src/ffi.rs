@@ -1,11 +1,20 @@
 #[macro_export]
 macro_rules! define_curve_ffi {
-    ($curve_name:ident, $Curve:ty, $Fr:ty, $Projective:ty, $FR_SIZE: expr, $PROJECTIVE_SIZE:expr) => {
-        use paste::paste;
-        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
-        use num_traits::Zero;
+    ($curve_name:ident, $Curve:ty, $Fr:ty, $Projective:ty, $FR_SIZE:literal, $PROJECTIVE_SIZE:literal) => {
+        use $crate::__private::paste::paste;
+        use $crate::__private::ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+        use $crate::__private::num_traits::Zero;
         use std::ops::Add;
-        use crate::ffi_prelude::{CReturn, CErrorCode};
+        use $crate::{CReturn, CErrorCode};
+        // the trait may already be in scope where the macro is invoked
+        #[allow(unused_imports)]
+        use $crate::StealthAddressOnCurve as _;
+
+        // the sizes are literals for cbindgen, which cannot evaluate the constants of the trait
+        const _: () = assert!(
+            $FR_SIZE == <$Curve as $crate::StealthAddressOnCurve>::SCALAR_SIZE
+                && $PROJECTIVE_SIZE == <$Curve as $crate::StealthAddressOnCurve>::COMPRESSED_POINT_SIZE
+        );
 
         paste! {
             #[repr(C)]
@@ -46,7 +55,7 @@ macro_rules! define_curve_ffi {
                 }
 
                 fn is_zero(&self) -> bool {
-                    <$Fr>::is_zero(&<$Fr>::from(self))
+                    <$Fr>::try_from(self).is_ok_and(|v| v.is_zero())
                 }
             }
 
@@ -70,9 +79,11 @@ macro_rules! define_curve_ffi {
                 }
             }
 
-            impl From<&[<$curve_name Fr>]> for $Fr {
-                fn from(value: &[<$curve_name Fr>]) -> Self {
-                    <$Fr>::deserialize_compressed(value.0.as_slice()).unwrap()
+            impl TryFrom<&[<$curve_name Fr>]> for $Fr {
+                type Error = SerializationError;
+
+                fn try_from(value: &[<$curve_name Fr>]) -> Result<Self, Self::Error> {
+                    <$Fr>::deserialize_compressed(value.0.as_slice())
                 }
             }
 
@@ -92,18 +103,19 @@ macro_rules! define_curve_ffi {
                 }
 
                 fn is_zero(&self) -> bool {
-                    <$Projective>::is_zero(&<$Projective>::from(self))
+                    <$Projective>::try_from(self).is_ok_and(|v| v.is_zero())
                 }
             }
 
             impl TryFrom<$Projective> for [<$curve_name Projective>] {
                 type Error = SerializationError;
 
+                // the struct has a fixed size, so shorter encodings, such as the single byte of the
+                // point at infinity in SEC1, are padded with zeros
                 fn try_from(value: $Projective) -> Result<Self, Self::Error> {
-                    let mut buf = Vec::new();
-                    value.serialize_compressed(&mut buf)?;
+                    let encoded = <$Curve as $crate::StealthAddressOnCurve>::encode_point(&value);
                     let mut result = [0u8; $PROJECTIVE_SIZE];
-                    result.copy_from_slice(&buf);
+                    result[..encoded.len()].copy_from_slice(&encoded);
                     Ok([<$curve_name Projective>](result))
                 }
             }
@@ -112,13 +124,22 @@ macro_rules! define_curve_ffi {
                 type Error = SerializationError;
 
                 fn try_from(value: [<$curve_name Projective>]) -> Result<Self, Self::Error> {
-                    <$Projective>::deserialize_compressed(value.0.as_slice())
+                    <$Projective>::try_from(&value)
                 }
             }
 
-            impl From<&[<$curve_name Projective>]> for $Projective {
-                fn from(value: &[<$curve_name Projective>]) -> Self {
-                    <$Projective>::deserialize_compressed(value.0.as_slice()).unwrap()
+            impl TryFrom<&[<$curve_name Projective>]> for $Projective {
+                type Error = SerializationError;
+
+                fn try_from(value: &[<$curve_name Projective>]) -> Result<Self, Self::Error> {
+                    <$Curve as $crate::StealthAddressOnCurve>::decode_point(value.0.as_slice())
+                        .or_else(|err| match value.0 {
+                            [0, ref padding @ ..] if padding.iter().all(|byte| *byte == 0) => {
+                                <$Curve as $crate::StealthAddressOnCurve>::decode_point(&[0])
+                            }
+                            _ => Err(err),
+                        })
+                        .map_err(|_| SerializationError::InvalidData)
                 }
             }
 
@@ -172,6 +193,7 @@ macro_rules! define_curve_ffi {
                 Box::into_raw(Box::new(res))
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<drop_ $curve_name _ffi_generate_random_fr>](ptr: *mut CReturn<[<$curve_name Fr>]>) {
                 if ptr.is_null() {
@@ -182,6 +204,7 @@ macro_rules! define_curve_ffi {
                 }
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<$curve_name _ffi_derive_public_key>](private_key: *mut [<$curve_name Fr>]) -> *mut CReturn<[<$curve_name Projective>]> {
                 let private_key = unsafe {
@@ -216,6 +239,7 @@ macro_rules! define_curve_ffi {
                 Box::into_raw(Box::new(res))
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<drop_ $curve_name _ffi_derive_public_key>](ptr: *mut CReturn<[<$curve_name Projective>]>) {
                 if ptr.is_null() {
@@ -257,6 +281,7 @@ macro_rules! define_curve_ffi {
                 Box::into_raw(Box::new(res))
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<drop_ $curve_name _ffi_random_keypair>](ptr: *mut CReturn<[<$curve_name KeyPair>]>) {
                 if ptr.is_null() {
@@ -267,6 +292,7 @@ macro_rules! define_curve_ffi {
                 }
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<$curve_name _ffi_generate_stealth_address>](
                 viewing_public_key: *mut [<$curve_name Projective>],
@@ -347,6 +373,7 @@ macro_rules! define_curve_ffi {
                 Box::into_raw(Box::new(res))
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<drop_ $curve_name _ffi_generate_stealth_address>](ptr: *mut CReturn<[<$curve_name StealthAddress>]>) {
                 if ptr.is_null() {
@@ -357,11 +384,12 @@ macro_rules! define_curve_ffi {
                 }
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<$curve_name _ffi_generate_stealth_private_key>](
                 ephemeral_public_key: *mut [<$curve_name Projective>],
+                viewing_key: *mut [<$curve_name Fr>],
                 spending_key: *mut [<$curve_name Fr>],
-                    viewing_key: *mut [<$curve_name Fr>],
                 view_tag: *mut u64,
             ) -> *mut CReturn<[<$curve_name Fr>]> {
                 let ephemeral_public_key = unsafe {
@@ -373,23 +401,23 @@ macro_rules! define_curve_ffi {
                     }
                     &*ephemeral_public_key
                 };
-                let spending_key = unsafe {
-                    if spending_key.is_null() {
+                let viewing_key = unsafe {
+                    if viewing_key.is_null() {
                         return Box::into_raw(Box::new(CReturn {
                             value: [<$curve_name Fr>]::zero(),
                             err_code: CErrorCode::InvalidKeys,
                         }));
                     }
-                    &*spending_key
+                    &*viewing_key
                 };
-                let viewing_key = unsafe {
-                    if viewing_key.is_null() {
+                let spending_key = unsafe {
+                    if spending_key.is_null() {
                         return Box::into_raw(Box::new(CReturn {
                             value: [<$curve_name Fr>]::zero(),
                             err_code: CErrorCode::InvalidKeys,
                         }));
                     }
-                    &*viewing_key
+                    &*spending_key
                 };
                 let view_tag = unsafe {
                     if view_tag.is_null() {
@@ -410,7 +438,7 @@ macro_rules! define_curve_ffi {
                         }))
                     }
                 };
-                let spending_key: $Fr = match spending_key.try_into() {
+                let viewing_key: $Fr = match viewing_key.try_into() {
                     Ok(v) => v,
                     Err(_) => {
                         return Box::into_raw(Box::new(CReturn {
@@ -419,7 +447,7 @@ macro_rules! define_curve_ffi {
                         }))
                     }
                 };
-                let viewing_key: $Fr = match viewing_key.try_into() {
+                let spending_key: $Fr = match spending_key.try_into() {
                     Ok(v) => v,
                     Err(_) => {
                         return Box::into_raw(Box::new(CReturn {
@@ -429,12 +457,22 @@ macro_rules! define_curve_ffi {
                     }
                 };
 
-                let res = match [<$curve_name Fr>]::try_from(<$Curve>::generate_stealth_private_key(
+                let stealth_private_key = match <$Curve>::generate_stealth_private_key(
                     ephemeral_public_key,
-                    spending_key,
                     viewing_key,
+                    spending_key,
                     *view_tag,
-                ).unwrap()) {
+                ) {
+                    Some(v) => v,
+                    None => {
+                        return Box::into_raw(Box::new(CReturn {
+                            value: [<$curve_name Fr>]::zero(),
+                            err_code: CErrorCode::ViewTagMismatch,
+                        }))
+                    }
+                };
+
+                let res = match [<$curve_name Fr>]::try_from(stealth_private_key) {
                     Ok(v) => CReturn {
                         value: v,
                         err_code: CErrorCode::NoError,
@@ -447,6 +485,7 @@ macro_rules! define_curve_ffi {
                 Box::into_raw(Box::new(res))
             }
 
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             #[no_mangle]
             pub extern "C" fn [<drop_ $curve_name _ffi_generate_stealth_private_key>](ptr: *mut CReturn<[<$curve_name Fr>]>) {
                 if ptr.is_null() {
@@ -461,7 +500,7 @@ macro_rules! define_curve_ffi {
             mod ffi_tests {
 
                 use super::*;
-                use ark_ec::CurveGroup;
+                use $crate::__private::ark_ec::CurveGroup;
 
                 #[test]
                 fn generate_random_fr_happy_path() {
@@ -547,6 +586,326 @@ macro_rules! define_curve_ffi {
                      [<drop_ $curve_name _ffi_generate_stealth_address>](stealth_address_payload_raw);
                      [<drop_ $curve_name _ffi_derive_public_key>](derived_address_raw);
                 }
+
+                // Conformance tests: every exported symbol must agree byte-for-byte with the
+                // Rust api on identical inputs, and must surface bad inputs as error codes.
+
+                fn ffi_fr(value: $Fr) -> [<$curve_name Fr>] {
+                    [<$curve_name Fr>]::try_from(value).unwrap()
+                }
+
+                fn ffi_projective(value: $Projective) -> [<$curve_name Projective>] {
+                    [<$curve_name Projective>]::try_from(value).unwrap()
+                }
+
+                // The scalar field modulus itself, which is never a canonical encoding
+                fn non_canonical_fr() -> [<$curve_name Fr>] {
+                    use $crate::__private::ark_ff::{BigInteger, PrimeField};
+
+                    let modulus = <$Fr as PrimeField>::MODULUS.to_bytes_le();
+                    let mut bytes = [0u8; $FR_SIZE];
+                    bytes.copy_from_slice(&modulus[..$FR_SIZE]);
+                    [<$curve_name Fr>](bytes)
+                }
+
+                fn malformed_projective() -> [<$curve_name Projective>] {
+                    [<$curve_name Projective>]([0xffu8; $PROJECTIVE_SIZE])
+                }
+
+                fn assert_err_code<T>(
+                    res_raw: *mut CReturn<T>,
+                    expected: CErrorCode,
+                    drop_fn: extern "C" fn(*mut CReturn<T>),
+                ) {
+                    let res = unsafe { &*res_raw };
+                    assert_eq!(res.err_code, expected);
+                    drop_fn(res_raw);
+                }
+
+                #[test]
+                fn generate_random_fr_is_canonical() {
+                    let res_raw = [<$curve_name _ffi_generate_random_fr>]();
+                    let res = unsafe { &*res_raw };
+                    assert_eq!(res.err_code, CErrorCode::NoError);
+
+                    let value = $Fr::try_from(&res.value).unwrap();
+                    assert_eq!(ffi_fr(value).0, res.value.0);
+                    [<drop_ $curve_name _ffi_generate_random_fr>](res_raw);
+                }
+
+                #[test]
+                fn derive_public_key_matches_rust() {
+                    let private_key = $Curve::generate_random_fr();
+                    let expected = ffi_projective($Curve::derive_public_key(&private_key));
+
+                    let mut ffi_private_key = ffi_fr(private_key);
+                    let res_raw = [<$curve_name _ffi_derive_public_key>](&mut ffi_private_key);
+                    let res = unsafe { &*res_raw };
+
+                    assert_eq!(res.err_code, CErrorCode::NoError);
+                    assert_eq!(res.value, expected);
+                    [<drop_ $curve_name _ffi_derive_public_key>](res_raw);
+                }
+
+                #[test]
+                fn derive_public_key_rejects_invalid_input() {
+                    let drop_fn = [<drop_ $curve_name _ffi_derive_public_key>];
+
+                    assert_err_code(
+                        [<$curve_name _ffi_derive_public_key>](std::ptr::null_mut()),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_derive_public_key>](&mut non_canonical_fr()),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                }
+
+                #[test]
+                fn random_keypair_is_consistent() {
+                    let res_raw = [<$curve_name _ffi_random_keypair>]();
+                    let res = unsafe { &*res_raw };
+                    assert_eq!(res.err_code, CErrorCode::NoError);
+
+                    let private_key = $Fr::try_from(&res.value.private_key).unwrap();
+                    assert_eq!(
+                        ffi_projective($Curve::derive_public_key(&private_key)),
+                        res.value.public_key
+                    );
+                    [<drop_ $curve_name _ffi_random_keypair>](res_raw);
+                }
+
+                #[test]
+                fn generate_stealth_address_matches_rust() {
+                    let (_, viewing_public_key) = $Curve::random_keypair();
+                    let (_, spending_public_key) = $Curve::random_keypair();
+                    let ephemeral_private_key = $Curve::generate_random_fr();
+
+                    let (expected_address, expected_view_tag) = $Curve::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    );
+
+                    let res_raw = [<$curve_name _ffi_generate_stealth_address>](
+                        &mut ffi_projective(viewing_public_key),
+                        &mut ffi_projective(spending_public_key),
+                        &mut ffi_fr(ephemeral_private_key),
+                    );
+                    let res = unsafe { &*res_raw };
+
+                    assert_eq!(res.err_code, CErrorCode::NoError);
+                    assert_eq!(res.value.stealth_address, ffi_projective(expected_address));
+                    assert_eq!(res.value.view_tag, expected_view_tag);
+                    [<drop_ $curve_name _ffi_generate_stealth_address>](res_raw);
+                }
+
+                #[test]
+                fn generate_stealth_address_rejects_invalid_input() {
+                    let drop_fn = [<drop_ $curve_name _ffi_generate_stealth_address>];
+                    let (_, public_key) = $Curve::random_keypair();
+                    let private_key = $Curve::generate_random_fr();
+
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            std::ptr::null_mut(),
+                            &mut ffi_projective(public_key),
+                            &mut ffi_fr(private_key),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            &mut ffi_projective(public_key),
+                            std::ptr::null_mut(),
+                            &mut ffi_fr(private_key),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            &mut ffi_projective(public_key),
+                            &mut ffi_projective(public_key),
+                            std::ptr::null_mut(),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            &mut malformed_projective(),
+                            &mut ffi_projective(public_key),
+                            &mut ffi_fr(private_key),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            &mut ffi_projective(public_key),
+                            &mut malformed_projective(),
+                            &mut ffi_fr(private_key),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_address>](
+                            &mut ffi_projective(public_key),
+                            &mut ffi_projective(public_key),
+                            &mut non_canonical_fr(),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                }
+
+                #[test]
+                fn generate_stealth_private_key_matches_rust() {
+                    let (viewing_key, viewing_public_key) = $Curve::random_keypair();
+                    let (spending_key, spending_public_key) = $Curve::random_keypair();
+                    let (ephemeral_private_key, ephemeral_public_key) = $Curve::random_keypair();
+
+                    let (_, mut view_tag) = $Curve::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    );
+                    let expected = $Curve::generate_stealth_private_key(
+                        ephemeral_public_key,
+                        viewing_key,
+                        spending_key,
+                        view_tag,
+                    )
+                    .unwrap();
+
+                    let res_raw = [<$curve_name _ffi_generate_stealth_private_key>](
+                        &mut ffi_projective(ephemeral_public_key),
+                        &mut ffi_fr(viewing_key),
+                        &mut ffi_fr(spending_key),
+                        &mut view_tag,
+                    );
+                    let res = unsafe { &*res_raw };
+
+                    assert_eq!(res.err_code, CErrorCode::NoError);
+                    assert_eq!(res.value.0, ffi_fr(expected).0);
+                    [<drop_ $curve_name _ffi_generate_stealth_private_key>](res_raw);
+                }
+
+                #[test]
+                fn generate_stealth_private_key_rejects_mismatched_view_tag() {
+                    let (viewing_key, viewing_public_key) = $Curve::random_keypair();
+                    let (spending_key, spending_public_key) = $Curve::random_keypair();
+                    let (ephemeral_private_key, ephemeral_public_key) = $Curve::random_keypair();
+
+                    let (_, view_tag) = $Curve::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    );
+
+                    let res_raw = [<$curve_name _ffi_generate_stealth_private_key>](
+                        &mut ffi_projective(ephemeral_public_key),
+                        &mut ffi_fr(viewing_key),
+                        &mut ffi_fr(spending_key),
+                        &mut view_tag.wrapping_add(1),
+                    );
+                    let res = unsafe { &*res_raw };
+
+                    assert_eq!(res.err_code, CErrorCode::ViewTagMismatch);
+                    assert!(res.value.is_zero());
+                    [<drop_ $curve_name _ffi_generate_stealth_private_key>](res_raw);
+                }
+
+                #[test]
+                fn generate_stealth_private_key_rejects_invalid_input() {
+                    let drop_fn = [<drop_ $curve_name _ffi_generate_stealth_private_key>];
+                    let (private_key, public_key) = $Curve::random_keypair();
+
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            std::ptr::null_mut(),
+                            &mut ffi_fr(private_key),
+                            &mut ffi_fr(private_key),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut ffi_projective(public_key),
+                            std::ptr::null_mut(),
+                            &mut ffi_fr(private_key),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut ffi_projective(public_key),
+                            &mut ffi_fr(private_key),
+                            std::ptr::null_mut(),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut ffi_projective(public_key),
+                            &mut ffi_fr(private_key),
+                            &mut ffi_fr(private_key),
+                            std::ptr::null_mut(),
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut malformed_projective(),
+                            &mut ffi_fr(private_key),
+                            &mut ffi_fr(private_key),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut ffi_projective(public_key),
+                            &mut non_canonical_fr(),
+                            &mut ffi_fr(private_key),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                    assert_err_code(
+                        [<$curve_name _ffi_generate_stealth_private_key>](
+                            &mut ffi_projective(public_key),
+                            &mut ffi_fr(private_key),
+                            &mut non_canonical_fr(),
+                            &mut 0,
+                        ),
+                        CErrorCode::InvalidKeys,
+                        drop_fn,
+                    );
+                }
+
+                #[test]
+                fn drop_functions_accept_null_pointers() {
+                    [<drop_ $curve_name _ffi_generate_random_fr>](std::ptr::null_mut());
+                    [<drop_ $curve_name _ffi_derive_public_key>](std::ptr::null_mut());
+                    [<drop_ $curve_name _ffi_random_keypair>](std::ptr::null_mut());
+                    [<drop_ $curve_name _ffi_generate_stealth_address>](std::ptr::null_mut());
+                    [<drop_ $curve_name _ffi_generate_stealth_private_key>](std::ptr::null_mut());
+                }
             }
 
         }
@@ -1,5 +1,6 @@
 use ark_serialize::SerializationError;
 
+/// The error codes returned by the FFI functions.
 #[repr(C)]
 #[derive(Debug, PartialOrd, PartialEq)]
 pub enum CErrorCode {
@@ -9,6 +10,7 @@ pub enum CErrorCode {
     SerializationErrorUnexpectedFlags = 3,
     SerializationErrorIoError = 4,
     InvalidKeys = 5,
+    ViewTagMismatch = 6,
 }
 
 impl From<SerializationError> for CErrorCode {
@@ -22,9 +24,10 @@ impl From<SerializationError> for CErrorCode {
     }
 }
 
+/// The result of an FFI function, to be freed with its `drop_` function.
 #[repr(C)]
 #[derive(Debug)]
 pub struct CReturn<T> {
-    pub(crate) value: T,
-    pub(crate) err_code: CErrorCode,
+    pub value: T,
+    pub err_code: CErrorCode,
 }
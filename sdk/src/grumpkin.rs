@@ -0,0 +1,54 @@
+//! The Grumpkin curve, y^2 = x^3 - 17 over the scalar field of BN254.
+//!
+//! Its scalar field is in turn the base field of BN254, so the two curves form a cycle.
+//! There is no arkworks 0.4 release of it, so it is defined here over the BN254 fields.
+use ark_bn254::{Fq as Fr, Fr as Fq};
+use ark_ec::{
+    models::CurveConfig,
+    short_weierstrass::{self, SWCurveConfig},
+};
+use ark_ff::MontFp;
+
+pub type Affine = short_weierstrass::Affine<GrumpkinConfig>;
+pub type Projective = short_weierstrass::Projective<GrumpkinConfig>;
+
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct GrumpkinConfig;
+
+impl CurveConfig for GrumpkinConfig {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fr = MontFp!("1");
+}
+
+impl SWCurveConfig for GrumpkinConfig {
+    const COEFF_A: Fq = MontFp!("0");
+    const COEFF_B: Fq = MontFp!("-17");
+    /// (1, sqrt(-16)), as used by Aztec and Noir.
+    const GENERATOR: Affine = Affine::new_unchecked(
+        MontFp!("1"),
+        MontFp!("17631683881184975370165255887551781615748388533673675138860"),
+    );
+
+    #[inline(always)]
+    fn mul_by_a(_: Fq) -> Fq {
+        MontFp!("0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{PrimeField, Zero};
+
+    #[test]
+    fn generator_has_the_bn254_base_field_order() {
+        let generator = Affine::generator();
+        assert!(generator.is_on_curve());
+        assert!(generator.mul_bigint(Fr::MODULUS).is_zero());
+        assert!(!(generator * Fr::from(2u8)).into_affine().is_zero());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{define_curve_tests, grumpkin::Projective, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the Grumpkin curve, whose base field
+/// is the scalar field of BN254.
+use ark_bn254::Fq as Fr;
+
+pub struct Grumpkin;
+
+impl StealthAddressOnCurve for Grumpkin {
+    type Projective = Projective;
+    const NAME: &'static str = "grumpkin";
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(grumpkin, Grumpkin, Fr, Projective, 32, 32);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Grumpkin, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Grumpkin, Fr, Projective);
+define_curve_tests!(Grumpkin);
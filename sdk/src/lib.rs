@@ -1,36 +1,114 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod announcement;
+mod dyn_curve;
+mod encoding;
 mod macros;
+mod meta_address;
+mod metadata;
+pub mod prelude;
+#[cfg(any(feature = "secp256k1", feature = "secp256r1"))]
+mod sec1;
 mod stealth_addresses;
+mod stealth_curve;
 
 #[cfg(feature = "baby_jub_jub")]
 #[cfg_attr(docsrs, doc(cfg(feature = "baby_jub_jub")))]
 mod baby_jub_jub_impl;
+#[cfg(feature = "bandersnatch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bandersnatch")))]
+mod bandersnatch_impl;
+#[cfg(feature = "bls12_377")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_377")))]
+mod bls12_377_g2_impl;
 #[cfg(feature = "bls12_377")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bls12_377")))]
 mod bls12_377_impl;
 #[cfg(feature = "bls12_381")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+mod bls12_381_g2_impl;
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
 mod bls12_381_impl;
 #[cfg(feature = "bn254")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bn254")))]
+mod bn254_g2_impl;
+#[cfg(feature = "bn254")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bn254")))]
 mod bn254_impl;
 #[cfg(feature = "bw6_761")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bw6_761")))]
+mod bw6_761_g2_impl;
+#[cfg(feature = "bw6_761")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bw6_761")))]
 mod bw6_761_impl;
+#[cfg(feature = "ed25519")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ed25519")))]
+mod ed25519_impl;
+#[cfg(feature = "ed_on_bls12_377")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ed_on_bls12_377")))]
+mod ed_on_bls12_377_impl;
+#[cfg(feature = "grumpkin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grumpkin")))]
+mod grumpkin;
+#[cfg(feature = "grumpkin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grumpkin")))]
+mod grumpkin_impl;
+#[cfg(feature = "jubjub")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jubjub")))]
+mod jubjub_impl;
 #[cfg(feature = "pallas")]
 #[cfg_attr(docsrs, doc(cfg(feature = "pallas")))]
 mod pallas_impl;
+#[cfg(feature = "ristretto255")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ristretto255")))]
+mod ristretto;
+#[cfg(feature = "ristretto255")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ristretto255")))]
+mod ristretto255_impl;
 #[cfg(feature = "secp256k1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
 mod secp256k1_impl;
 #[cfg(feature = "secp256r1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "secp256r1")))]
 mod secp256r1_impl;
+#[cfg(feature = "secq256k1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secq256k1")))]
+mod secq256k1_impl;
+#[cfg(feature = "stark_curve")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stark_curve")))]
+mod stark_curve;
+#[cfg(feature = "stark_curve")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stark_curve")))]
+mod stark_curve_impl;
 #[cfg(feature = "vesta")]
 #[cfg_attr(docsrs, doc(cfg(feature = "vesta")))]
 mod vesta_impl;
 
+#[cfg(feature = "chain_address")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chain_address")))]
+pub mod chain_address;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod compressed;
+
+#[cfg(feature = "ethereum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ethereum")))]
+pub mod ethereum;
+
+#[cfg(feature = "stark_curve")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stark_curve")))]
+pub mod starknet;
+
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+pub mod text;
+
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+pub mod uri;
+
 #[cfg(feature = "ffi")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
 mod ffi;
@@ -38,19 +116,110 @@ mod ffi;
 #[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
 mod ffi_prelude;
 
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+mod python;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+mod python_prelude;
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+mod wasm;
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+mod wasm_prelude;
+
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub use ffi_prelude::{CErrorCode, CReturn};
+
+pub use announcement::Announcement;
+pub use dyn_curve::{curves, find_curve, find_curve_by_scheme_id, DynCurve, DynStealthCurve};
+pub use encoding::InvalidEncoding;
+pub use meta_address::{InvalidMetaAddress, MetaAddress};
+pub use metadata::{AnnouncementMetadata, InvalidMetadata, Transfer, TRANSFER_METADATA_SIZE};
+
 #[cfg(feature = "baby_jub_jub")]
 #[cfg_attr(docsrs, doc(cfg(feature = "baby_jub_jub")))]
 pub use baby_jub_jub_impl::BabyJubJub;
+#[cfg(feature = "bandersnatch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bandersnatch")))]
+pub use bandersnatch_impl::Bandersnatch;
+#[cfg(feature = "bls12_377")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_377")))]
+pub use bls12_377_g2_impl::Bls12_377G2;
+#[cfg(feature = "bls12_377")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_377")))]
+pub use bls12_377_impl::Bls12_377;
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub use bls12_381_g2_impl::Bls12_381G2;
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub use bls12_381_impl::Bls12_381;
+#[cfg(feature = "bn254")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bn254")))]
+pub use bn254_g2_impl::Bn254G2;
+#[cfg(feature = "bn254")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bn254")))]
+pub use bn254_impl::Bn254;
+#[cfg(feature = "bw6_761")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bw6_761")))]
+pub use bw6_761_g2_impl::BW6_761G2;
+#[cfg(feature = "bw6_761")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bw6_761")))]
+pub use bw6_761_impl::BW6_761;
+#[cfg(feature = "ed25519")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ed25519")))]
+pub use ed25519_impl::Ed25519;
+#[cfg(feature = "ed_on_bls12_377")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ed_on_bls12_377")))]
+pub use ed_on_bls12_377_impl::EdOnBls12_377;
+#[cfg(feature = "grumpkin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grumpkin")))]
+pub use grumpkin_impl::Grumpkin;
+#[cfg(feature = "jubjub")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jubjub")))]
+pub use jubjub_impl::Jubjub;
 #[cfg(feature = "pallas")]
 #[cfg_attr(docsrs, doc(cfg(feature = "pallas")))]
 pub use pallas_impl::Pallas;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub use python_prelude::register_python_module;
+#[cfg(feature = "ristretto255")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ristretto255")))]
+pub use ristretto::{RistrettoAffine, RistrettoPoint};
+#[cfg(feature = "ristretto255")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ristretto255")))]
+pub use ristretto255_impl::Ristretto255;
 #[cfg(feature = "secp256k1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
 pub use secp256k1_impl::Secp256k1;
 #[cfg(feature = "secp256r1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "secp256r1")))]
 pub use secp256r1_impl::Secp256r1;
-pub use stealth_addresses::StealthAddressOnCurve;
+#[cfg(feature = "secq256k1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secq256k1")))]
+pub use secq256k1_impl::Secq256k1;
+#[cfg(feature = "stark_curve")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stark_curve")))]
+pub use stark_curve_impl::StarkCurve;
+pub use stealth_addresses::{HasViewTag, StealthAddressOnCurve};
+pub use stealth_curve::StealthCurve;
 #[cfg(feature = "vesta")]
 #[cfg_attr(docsrs, doc(cfg(feature = "vesta")))]
 pub use vesta_impl::Vesta;
+
+/// The dependencies of the exported macros, so that crates invoking them need not depend on
+/// the same versions themselves. Not part of the public api.
+#[doc(hidden)]
+pub mod __private {
+    pub use ark_ec;
+    pub use ark_ff;
+    pub use ark_serialize;
+    pub use num_traits;
+    #[cfg(feature = "ffi")]
+    pub use paste;
+}
@@ -4,7 +4,9 @@ macro_rules! define_curve_tests {
         #[cfg(test)]
         mod tests {
             use super::*;
-            use ark_ec::CurveGroup;
+            use $crate::__private::ark_ec::CurveGroup;
+            #[allow(unused_imports)]
+            use $crate::StealthAddressOnCurve as _;
 
             #[test]
             fn random_keypair_happy_path() {
@@ -32,11 +34,7 @@ macro_rules! define_curve_tests {
                 let shared2 = <$Curve>::compute_shared_point(key2, pub_key1);
 
                 // Convert Projective to Affine for equality comparison
-                let shared1_affine = shared1.into_affine();
-                let shared2_affine = shared2.into_affine();
-
-                assert_eq!(shared1_affine.x, shared2_affine.x);
-                assert_eq!(shared1_affine.y, shared2_affine.y);
+                assert_eq!(shared1.into_affine(), shared2.into_affine());
             }
 
             #[test]
@@ -68,6 +66,154 @@ macro_rules! define_curve_tests {
                     <$Curve>::derive_public_key(&stealth_private_key_opt.unwrap());
                 assert_eq!(derived_address, stealth_address);
             }
+
+            #[test]
+            fn check_stealth_address_happy_path() {
+                let (_, spending_public_key) = <$Curve>::random_keypair();
+                let (viewing_key, viewing_public_key) = <$Curve>::random_keypair();
+                let (ephemeral_private_key, ephemeral_public_key) = <$Curve>::random_keypair();
+
+                let (stealth_address, view_tag) = <$Curve>::generate_stealth_address(
+                    viewing_public_key,
+                    spending_public_key,
+                    ephemeral_private_key,
+                );
+
+                assert_eq!(
+                    <$Curve>::check_stealth_address(
+                        ephemeral_public_key,
+                        viewing_key,
+                        spending_public_key,
+                        view_tag
+                    ),
+                    Some(stealth_address)
+                );
+                assert_eq!(
+                    <$Curve>::check_stealth_address(
+                        ephemeral_public_key,
+                        viewing_key,
+                        spending_public_key,
+                        view_tag.wrapping_add(1)
+                    ),
+                    None
+                );
+            }
+
+            #[test]
+            fn scan_happy_path() {
+                let (spending_key, spending_public_key) = <$Curve>::random_keypair();
+                let (viewing_key, viewing_public_key) = <$Curve>::random_keypair();
+                let (_, other_viewing_public_key) = <$Curve>::random_keypair();
+
+                // only the second announcement is addressed to the recipient
+                let mut announcements = Vec::new();
+                for viewing_public_key in [other_viewing_public_key, viewing_public_key] {
+                    let (ephemeral_private_key, ephemeral_public_key) = <$Curve>::random_keypair();
+                    let (stealth_address, view_tag) = <$Curve>::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    );
+                    announcements.push((stealth_address, ephemeral_public_key, view_tag));
+                }
+                // a copy of it whose view tag matches, but whose stealth address does not
+                let (_, ephemeral_public_key, view_tag) = announcements[1];
+                announcements.push((spending_public_key, ephemeral_public_key, view_tag));
+
+                let found = <$Curve>::scan(&announcements, viewing_key, spending_key);
+                assert_eq!(found.len(), 1);
+                assert_eq!(found[0].0, 1);
+                assert_eq!(<$Curve>::derive_public_key(&found[0].1), announcements[1].0);
+            }
+
+            #[test]
+            fn scan_with_metadata_reports_the_transfer() {
+                let (spending_key, spending_public_key) = <$Curve>::random_keypair();
+                let (viewing_key, viewing_public_key) = <$Curve>::random_keypair();
+                let (ephemeral_private_key, ephemeral_public_key) = <$Curve>::random_keypair();
+                let transfer = $crate::Transfer::Erc20 {
+                    token: [1; 20],
+                    amount: [2; 32],
+                };
+                let (stealth_address, metadata) = <$Curve>::generate_stealth_address_with_metadata(
+                    viewing_public_key,
+                    spending_public_key,
+                    ephemeral_private_key,
+                    Some(transfer),
+                );
+                assert_eq!(
+                    stealth_address,
+                    <$Curve>::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    )
+                    .0
+                );
+                let metadata = metadata.encode();
+                let mut mismatched = metadata.clone();
+                mismatched[0] = mismatched[0].wrapping_add(1);
+
+                // the view tag byte matches the second announcement by construction, but not its
+                // stealth address
+                let announcements = [
+                    (stealth_address, ephemeral_public_key, mismatched.as_slice()),
+                    (
+                        spending_public_key,
+                        ephemeral_public_key,
+                        metadata.as_slice(),
+                    ),
+                    (stealth_address, ephemeral_public_key, metadata.as_slice()),
+                ];
+                let found = <$Curve>::scan_with_metadata(&announcements, viewing_key, spending_key);
+                assert_eq!(found.len(), 1);
+                assert_eq!(found[0].0, 2);
+                assert_eq!(<$Curve>::derive_public_key(&found[0].1), stealth_address);
+                assert_eq!(found[0].2.transfer, Some(transfer));
+            }
+
+            #[test]
+            fn sizes_match_the_encodings() {
+                use $crate::__private::ark_serialize::CanonicalSerialize;
+
+                let (private_key, public_key) = <$Curve>::random_keypair();
+                assert_eq!(private_key.compressed_size(), <$Curve>::SCALAR_SIZE);
+                assert_eq!(
+                    <$Curve>::encode_point(&public_key).len(),
+                    <$Curve>::COMPRESSED_POINT_SIZE
+                );
+                assert_eq!(
+                    <$Curve>::encode_point_uncompressed(&public_key).len(),
+                    <$Curve>::UNCOMPRESSED_POINT_SIZE
+                );
+                assert_eq!(
+                    <$Curve>::VIEW_TAG_SIZE,
+                    std::mem::size_of_val(
+                        &<$Curve>::generate_stealth_address(public_key, public_key, private_key).1
+                    )
+                );
+            }
+
+            #[test]
+            fn point_encodings_round_trip() {
+                use $crate::__private::num_traits::Zero;
+
+                let (_, public_key) = <$Curve>::random_keypair();
+                let identity = <$Curve as $crate::StealthAddressOnCurve>::Projective::zero();
+                for point in [public_key, identity] {
+                    let compressed = <$Curve>::encode_point(&point);
+                    assert_eq!(<$Curve>::decode_point(&compressed), Ok(point));
+                    let uncompressed = <$Curve>::encode_point_uncompressed(&point);
+                    assert_eq!(
+                        <$Curve>::decode_point_uncompressed(&uncompressed),
+                        Ok(point)
+                    );
+                }
+
+                let compressed = <$Curve>::encode_point(&public_key);
+                assert!(<$Curve>::decode_point(&compressed[1..]).is_err());
+                assert!(<$Curve>::decode_point(&[compressed.as_slice(), &[0]].concat()).is_err());
+            }
         }
     };
 }
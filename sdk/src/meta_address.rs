@@ -0,0 +1,108 @@
+use crate::announcement::Announcement;
+use crate::prelude::Fr;
+use crate::stealth_addresses::{HasViewTag, StealthAddressOnCurve};
+use std::fmt::{Debug, Display, Formatter};
+
+/// The public keys a recipient publishes, for senders to derive stealth addresses from.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct MetaAddress<C: StealthAddressOnCurve> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::compressed::serialize_point::<C, _>",
+            deserialize_with = "crate::compressed::deserialize_point::<C, _>"
+        )
+    )]
+    pub spending_public_key: C::Projective,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::compressed::serialize_point::<C, _>",
+            deserialize_with = "crate::compressed::deserialize_point::<C, _>"
+        )
+    )]
+    pub viewing_public_key: C::Projective,
+}
+
+impl<C: StealthAddressOnCurve> MetaAddress<C> {
+    pub fn new(spending_public_key: C::Projective, viewing_public_key: C::Projective) -> Self {
+        MetaAddress {
+            spending_public_key,
+            viewing_public_key,
+        }
+    }
+
+    /// Generates a stealth address for the recipient, announced along with the ephemeral
+    /// public key.
+    pub fn generate_stealth_address(&self, ephemeral_private_key: Fr<C>) -> Announcement<C>
+    where
+        Fr<C>: HasViewTag,
+    {
+        let (stealth_address, view_tag) = C::generate_stealth_address(
+            self.viewing_public_key,
+            self.spending_public_key,
+            ephemeral_private_key,
+        );
+        Announcement::new(
+            stealth_address,
+            C::derive_public_key(&ephemeral_private_key),
+            view_tag,
+        )
+    }
+}
+
+// implemented by hand, as deriving would require the curve marker to implement them too
+impl<C: StealthAddressOnCurve> Clone for MetaAddress<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: StealthAddressOnCurve> Copy for MetaAddress<C> {}
+
+impl<C: StealthAddressOnCurve> PartialEq for MetaAddress<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.spending_public_key == other.spending_public_key
+            && self.viewing_public_key == other.viewing_public_key
+    }
+}
+
+impl<C: StealthAddressOnCurve> Eq for MetaAddress<C> {}
+
+impl<C: StealthAddressOnCurve> Debug for MetaAddress<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetaAddress")
+            .field("spending_public_key", &self.spending_public_key)
+            .field("viewing_public_key", &self.viewing_public_key)
+            .finish()
+    }
+}
+
+/// Why an ERC-5564 style `st:<chain>:0x<keys>` meta-address could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidMetaAddress {
+    /// The text does not look like `st:<chain>:0x<keys>`.
+    Malformed,
+    /// The keys are not two public keys of the curve.
+    InvalidEncoding,
+}
+
+impl Display for InvalidMetaAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidMetaAddress::Malformed => {
+                write!(f, "the meta-address must look like st:<chain>:0x<keys>")
+            }
+            InvalidMetaAddress::InvalidEncoding => write!(
+                f,
+                "the meta-address keys are not two public keys on the curve"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidMetaAddress {}
@@ -0,0 +1,266 @@
+//! The metadata of ERC-5564 announcements: the view tag, followed, for payments in Ether or
+//! tokens, by a function selector, the token contract and the amount or token id, 57 bytes in all.
+use std::fmt::{Display, Formatter};
+
+/// The size of metadata that describes a transfer.
+pub const TRANSFER_METADATA_SIZE: usize = 1 + 4 + 20 + 32;
+
+/// The selector ERC-5564 uses in place of a function for transfers of Ether.
+const ETHER_SELECTOR: [u8; 4] = [0xee; 4];
+/// The address ERC-5564 uses in place of a token contract for transfers of Ether.
+const ETHER_ADDRESS: [u8; 20] = [0xee; 20];
+/// `transfer(address,uint256)`, with which ERC-20 tokens are sent.
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// `transferFrom(address,address,uint256)`, which ERC-20 and ERC-721 share.
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+/// `safeTransferFrom(address,address,uint256)`, with which ERC-721 tokens are sent.
+const SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x42, 0x84, 0x2e, 0x0e];
+
+/// What a payment transferred. Amounts and token ids are 256-bit big-endian integers, as in the
+/// metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transfer {
+    /// Ether.
+    Ether { amount: [u8; 32] },
+    /// ERC-20 tokens, sent with `transfer`.
+    Erc20 { token: [u8; 20], amount: [u8; 32] },
+    /// An ERC-721 token, sent with `safeTransferFrom`.
+    Erc721 { token: [u8; 20], token_id: [u8; 32] },
+    /// Tokens sent with `transferFrom`, which ERC-20 and ERC-721 share, so the value is an amount
+    /// or a token id depending on the contract.
+    TransferFrom { token: [u8; 20], value: [u8; 32] },
+}
+
+impl Transfer {
+    /// The token contract, or `None` for Ether.
+    pub fn token(&self) -> Option<[u8; 20]> {
+        match self {
+            Transfer::Ether { .. } => None,
+            Transfer::Erc20 { token, .. }
+            | Transfer::Erc721 { token, .. }
+            | Transfer::TransferFrom { token, .. } => Some(*token),
+        }
+    }
+
+    /// The amount, or the token id for ERC-721 tokens.
+    pub fn value(&self) -> [u8; 32] {
+        match self {
+            Transfer::Ether { amount } | Transfer::Erc20 { amount, .. } => *amount,
+            Transfer::Erc721 { token_id, .. } => *token_id,
+            Transfer::TransferFrom { value, .. } => *value,
+        }
+    }
+
+    fn selector(&self) -> [u8; 4] {
+        match self {
+            Transfer::Ether { .. } => ETHER_SELECTOR,
+            Transfer::Erc20 { .. } => TRANSFER_SELECTOR,
+            Transfer::Erc721 { .. } => SAFE_TRANSFER_FROM_SELECTOR,
+            Transfer::TransferFrom { .. } => TRANSFER_FROM_SELECTOR,
+        }
+    }
+}
+
+/// Metadata that is too short for its transfer, or too short to hold a view tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidMetadata;
+
+impl Display for InvalidMetadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not a valid ERC-5564 announcement metadata")
+    }
+}
+
+impl std::error::Error for InvalidMetadata {}
+
+/// The metadata of an announcement, as ERC-5564 lays it out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementMetadata {
+    /// The view tag byte, as `metadata_view_tag` derives it, e.g. the most significant byte of
+    /// the hashed shared secret for secp256k1.
+    pub view_tag: u8,
+    /// What was transferred, if the metadata describes a transfer.
+    pub transfer: Option<Transfer>,
+    /// Whatever follows, which the standard leaves to the application.
+    pub extra: Vec<u8>,
+}
+
+impl AnnouncementMetadata {
+    /// The metadata for a payment, given the view tag byte of `metadata_view_tag`, which
+    /// `generate_stealth_address_with_metadata` builds it with.
+    pub fn new(view_tag: u8, transfer: Option<Transfer>) -> Self {
+        AnnouncementMetadata {
+            view_tag,
+            transfer,
+            extra: Vec::new(),
+        }
+    }
+
+    /// Whether the metadata carries the view tag byte of `metadata_view_tag`.
+    pub fn matches_view_tag(&self, view_tag: u8) -> bool {
+        self.view_tag == view_tag
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TRANSFER_METADATA_SIZE + self.extra.len());
+        bytes.push(self.view_tag);
+        if let Some(transfer) = &self.transfer {
+            bytes.extend_from_slice(&transfer.selector());
+            bytes.extend_from_slice(&transfer.token().unwrap_or(ETHER_ADDRESS));
+            bytes.extend_from_slice(&transfer.value());
+        }
+        bytes.extend_from_slice(&self.extra);
+        bytes
+    }
+
+    /// Decodes metadata, reading a transfer if it starts with a selector the standard names, and
+    /// keeping whatever else follows the view tag as `extra`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, InvalidMetadata> {
+        let (&view_tag, rest) = bytes.split_first().ok_or(InvalidMetadata)?;
+        let selector = rest
+            .get(..4)
+            .and_then(|selector| <[u8; 4]>::try_from(selector).ok());
+        let known = [
+            ETHER_SELECTOR,
+            TRANSFER_SELECTOR,
+            TRANSFER_FROM_SELECTOR,
+            SAFE_TRANSFER_FROM_SELECTOR,
+        ];
+        let Some(selector) = selector.filter(|selector| known.contains(selector)) else {
+            return Ok(AnnouncementMetadata {
+                view_tag,
+                transfer: None,
+                extra: rest.to_vec(),
+            });
+        };
+        if bytes.len() < TRANSFER_METADATA_SIZE {
+            return Err(InvalidMetadata);
+        }
+
+        let token: [u8; 20] = bytes[5..25].try_into().unwrap();
+        let value: [u8; 32] = bytes[25..TRANSFER_METADATA_SIZE].try_into().unwrap();
+        let transfer = match selector {
+            ETHER_SELECTOR if token == ETHER_ADDRESS => Transfer::Ether { amount: value },
+            ETHER_SELECTOR => return Err(InvalidMetadata),
+            TRANSFER_SELECTOR => Transfer::Erc20 {
+                token,
+                amount: value,
+            },
+            SAFE_TRANSFER_FROM_SELECTOR => Transfer::Erc721 {
+                token,
+                token_id: value,
+            },
+            _ => Transfer::TransferFrom { token, value },
+        };
+        Ok(AnnouncementMetadata {
+            view_tag,
+            transfer: Some(transfer),
+            extra: bytes[TRANSFER_METADATA_SIZE..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiny_keccak::{Hasher, Keccak};
+
+    fn selector_of(signature: &str) -> [u8; 4] {
+        let mut hash = [0; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(signature.as_bytes());
+        hasher.finalize(&mut hash);
+        hash[..4].try_into().unwrap()
+    }
+
+    #[test]
+    fn selectors_match_their_functions() {
+        assert_eq!(selector_of("transfer(address,uint256)"), TRANSFER_SELECTOR);
+        assert_eq!(
+            selector_of("transferFrom(address,address,uint256)"),
+            TRANSFER_FROM_SELECTOR
+        );
+        assert_eq!(
+            selector_of("safeTransferFrom(address,address,uint256)"),
+            SAFE_TRANSFER_FROM_SELECTOR
+        );
+    }
+
+    #[test]
+    fn encodes_the_layout_of_the_standard() {
+        let mut amount = [0; 32];
+        amount[31] = 0x2a;
+        let metadata = AnnouncementMetadata::new(0x34, Some(Transfer::Ether { amount }));
+
+        let bytes = metadata.encode();
+        assert_eq!(bytes.len(), TRANSFER_METADATA_SIZE);
+        assert_eq!(bytes[0], 0x34);
+        assert_eq!(bytes[1..25], [0xee; 24]);
+        assert_eq!(bytes[25..], amount);
+        assert_eq!(AnnouncementMetadata::decode(&bytes), Ok(metadata));
+    }
+
+    #[test]
+    fn round_trips_transfers() {
+        let token = [0x11; 20];
+        let value = [0x22; 32];
+        let transfers = [
+            Transfer::Ether { amount: value },
+            Transfer::Erc20 {
+                token,
+                amount: value,
+            },
+            Transfer::Erc721 {
+                token,
+                token_id: value,
+            },
+            Transfer::TransferFrom { token, value },
+        ];
+        for transfer in transfers {
+            let mut metadata = AnnouncementMetadata::new(7, Some(transfer));
+            metadata.extra = b"memo".to_vec();
+            assert_eq!(
+                AnnouncementMetadata::decode(&metadata.encode()),
+                Ok(metadata)
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_unknown_metadata_as_extra() {
+        assert_eq!(
+            AnnouncementMetadata::decode(&[9, 1, 2, 3]),
+            Ok(AnnouncementMetadata {
+                view_tag: 9,
+                transfer: None,
+                extra: vec![1, 2, 3],
+            })
+        );
+        assert_eq!(
+            AnnouncementMetadata::decode(&[9]),
+            Ok(AnnouncementMetadata::new(9, None))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_metadata() {
+        assert_eq!(AnnouncementMetadata::decode(&[]), Err(InvalidMetadata));
+        let bytes = AnnouncementMetadata::new(
+            0,
+            Some(Transfer::Erc20 {
+                token: [1; 20],
+                amount: [2; 32],
+            }),
+        )
+        .encode();
+        assert_eq!(
+            AnnouncementMetadata::decode(&bytes[..TRANSFER_METADATA_SIZE - 1]),
+            Err(InvalidMetadata)
+        );
+        // Ether must be sent from the placeholder address
+        let mut bytes =
+            AnnouncementMetadata::new(0, Some(Transfer::Ether { amount: [0; 32] })).encode();
+        bytes[5] = 0;
+        assert_eq!(AnnouncementMetadata::decode(&bytes), Err(InvalidMetadata));
+    }
+}
@@ -6,10 +6,19 @@ pub struct Pallas;
 
 impl StealthAddressOnCurve for Pallas {
     type Projective = Projective;
+    const NAME: &'static str = "pallas";
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(pallas, Pallas, Fr, Projective, 32, 33);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Pallas, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Pallas, Fr, Projective);
 define_curve_tests!(Pallas);
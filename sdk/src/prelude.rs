@@ -0,0 +1,79 @@
+//! The stealth address traits and types, every compiled-in curve, and aliases for their keys, so that
+//! users need no arkworks dependencies of their own.
+//!
+//! ```
+//! # #[cfg(feature = "secp256k1")]
+//! # {
+//! use stealth_address_kit::prelude::*;
+//!
+//! let (spending_key, spending_public_key): (SecretKey<Secp256k1>, PublicKey<Secp256k1>) =
+//!     Secp256k1::random_keypair();
+//! let (viewing_key, viewing_public_key) = Secp256k1::random_keypair();
+//! let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+//!
+//! let (stealth_address, view_tag) = Secp256k1::generate_stealth_address(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//! );
+//! let stealth_private_key = Secp256k1::generate_stealth_private_key(
+//!     ephemeral_public_key,
+//!     viewing_key,
+//!     spending_key,
+//!     view_tag,
+//! )
+//! .unwrap();
+//! assert_eq!(Secp256k1::derive_public_key(&stealth_private_key), stealth_address);
+//! # }
+//! ```
+use ark_ec::{AffineRepr, CurveGroup};
+
+pub use crate::{
+    Announcement, AnnouncementMetadata, DynStealthCurve, HasViewTag, MetaAddress,
+    StealthAddressOnCurve, StealthCurve, Transfer,
+};
+
+#[cfg(feature = "baby_jub_jub")]
+pub use crate::BabyJubJub;
+#[cfg(feature = "bandersnatch")]
+pub use crate::Bandersnatch;
+#[cfg(feature = "ed25519")]
+pub use crate::Ed25519;
+#[cfg(feature = "ed_on_bls12_377")]
+pub use crate::EdOnBls12_377;
+#[cfg(feature = "grumpkin")]
+pub use crate::Grumpkin;
+#[cfg(feature = "jubjub")]
+pub use crate::Jubjub;
+#[cfg(feature = "pallas")]
+pub use crate::Pallas;
+#[cfg(feature = "ristretto255")]
+pub use crate::Ristretto255;
+#[cfg(feature = "secp256k1")]
+pub use crate::Secp256k1;
+#[cfg(feature = "secp256r1")]
+pub use crate::Secp256r1;
+#[cfg(feature = "secq256k1")]
+pub use crate::Secq256k1;
+#[cfg(feature = "stark_curve")]
+pub use crate::StarkCurve;
+#[cfg(feature = "vesta")]
+pub use crate::Vesta;
+#[cfg(feature = "bls12_377")]
+pub use crate::{Bls12_377, Bls12_377G2};
+#[cfg(feature = "bls12_381")]
+pub use crate::{Bls12_381, Bls12_381G2};
+#[cfg(feature = "bn254")]
+pub use crate::{Bn254, Bn254G2};
+#[cfg(feature = "bw6_761")]
+pub use crate::{BW6_761, BW6_761G2};
+
+/// The scalar field of the curve, e.g. `Fr<Secp256k1>`.
+pub type Fr<C> =
+    <<<C as StealthAddressOnCurve>::Projective as CurveGroup>::Affine as AffineRepr>::ScalarField;
+
+/// A point on the curve, used for public keys and stealth addresses.
+pub type PublicKey<C> = <C as StealthAddressOnCurve>::Projective;
+
+/// A private key on the curve, including stealth private keys.
+pub type SecretKey<C> = Fr<C>;
@@ -0,0 +1,143 @@
+#[macro_export]
+macro_rules! define_curve_python {
+    ($Curve:ident, $Fr:ty, $Projective:ty) => {
+        pub(crate) mod python {
+            use super::$Curve as Curve;
+            use super::*;
+            use pyo3::prelude::*;
+            use pyo3::types::PyBytes;
+            use $crate::python_prelude::{
+                from_py_bytes, point_from_py_bytes, point_to_py_bytes, to_py_bytes,
+            };
+
+            /// Exposes the StealthAddressOnCurve operations to python as static methods.
+            /// Private keys are exchanged as their canonical compressed bytes, and points in the
+            /// standard compressed encoding of the curve.
+            #[pyclass(module = "stealth_address_kit", frozen)]
+            pub struct $Curve;
+
+            #[pymethods]
+            impl $Curve {
+                #[classattr]
+                #[pyo3(name = "NAME")]
+                fn name() -> &'static str {
+                    Curve::NAME
+                }
+
+                #[classattr]
+                #[pyo3(name = "SCALAR_SIZE")]
+                fn scalar_size() -> usize {
+                    Curve::SCALAR_SIZE
+                }
+
+                #[classattr]
+                #[pyo3(name = "POINT_SIZE")]
+                fn point_size() -> usize {
+                    Curve::COMPRESSED_POINT_SIZE
+                }
+
+                #[staticmethod]
+                fn generate_random_fr(py: Python<'_>) -> Bound<'_, PyBytes> {
+                    to_py_bytes(py, &Curve::generate_random_fr())
+                }
+
+                #[staticmethod]
+                fn derive_public_key<'py>(
+                    py: Python<'py>,
+                    private_key: &[u8],
+                ) -> PyResult<Bound<'py, PyBytes>> {
+                    let private_key: $Fr = from_py_bytes(private_key)?;
+                    Ok(point_to_py_bytes::<Curve>(
+                        py,
+                        &Curve::derive_public_key(&private_key),
+                    ))
+                }
+
+                #[staticmethod]
+                fn random_keypair(py: Python<'_>) -> (Bound<'_, PyBytes>, Bound<'_, PyBytes>) {
+                    let (private_key, public_key) = Curve::random_keypair();
+                    (
+                        to_py_bytes(py, &private_key),
+                        point_to_py_bytes::<Curve>(py, &public_key),
+                    )
+                }
+
+                #[staticmethod]
+                fn generate_stealth_address<'py>(
+                    py: Python<'py>,
+                    viewing_public_key: &[u8],
+                    spending_public_key: &[u8],
+                    ephemeral_private_key: &[u8],
+                ) -> PyResult<(Bound<'py, PyBytes>, u64)> {
+                    let viewing_public_key = point_from_py_bytes::<Curve>(viewing_public_key)?;
+                    let spending_public_key = point_from_py_bytes::<Curve>(spending_public_key)?;
+                    let ephemeral_private_key: $Fr = from_py_bytes(ephemeral_private_key)?;
+
+                    let (stealth_address, view_tag) = Curve::generate_stealth_address(
+                        viewing_public_key,
+                        spending_public_key,
+                        ephemeral_private_key,
+                    );
+                    Ok((point_to_py_bytes::<Curve>(py, &stealth_address), view_tag))
+                }
+
+                /// Returns `None` when the view tag does not match.
+                #[staticmethod]
+                fn generate_stealth_private_key<'py>(
+                    py: Python<'py>,
+                    ephemeral_public_key: &[u8],
+                    viewing_key: &[u8],
+                    spending_key: &[u8],
+                    view_tag: u64,
+                ) -> PyResult<Option<Bound<'py, PyBytes>>> {
+                    let ephemeral_public_key = point_from_py_bytes::<Curve>(ephemeral_public_key)?;
+                    let viewing_key: $Fr = from_py_bytes(viewing_key)?;
+                    let spending_key: $Fr = from_py_bytes(spending_key)?;
+
+                    Ok(Curve::generate_stealth_private_key(
+                        ephemeral_public_key,
+                        viewing_key,
+                        spending_key,
+                        view_tag,
+                    )
+                    .map(|stealth_private_key| to_py_bytes(py, &stealth_private_key)))
+                }
+
+                /// Takes a list of `(stealth_address, ephemeral_public_key, view_tag)`
+                /// announcements, and returns `(index, stealth_private_key)` for every
+                /// announcement addressed to the recipient.
+                #[staticmethod]
+                fn scan<'py>(
+                    py: Python<'py>,
+                    announcements: Vec<(Vec<u8>, Vec<u8>, u64)>,
+                    viewing_key: &[u8],
+                    spending_key: &[u8],
+                ) -> PyResult<Vec<(usize, Bound<'py, PyBytes>)>> {
+                    let announcements = announcements
+                        .iter()
+                        .map(|(stealth_address, ephemeral_public_key, view_tag)| {
+                            Ok((
+                                point_from_py_bytes::<Curve>(stealth_address)?,
+                                point_from_py_bytes::<Curve>(ephemeral_public_key)?,
+                                *view_tag,
+                            ))
+                        })
+                        .collect::<PyResult<Vec<_>>>()?;
+                    let viewing_key: $Fr = from_py_bytes(viewing_key)?;
+                    let spending_key: $Fr = from_py_bytes(spending_key)?;
+
+                    Ok(Curve::scan(&announcements, viewing_key, spending_key)
+                        .into_iter()
+                        .map(|(index, stealth_private_key)| {
+                            (index, to_py_bytes(py, &stealth_private_key))
+                        })
+                        .collect())
+                }
+            }
+
+            pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+                m.add_class::<$Curve>()
+            }
+        }
+    };
+}
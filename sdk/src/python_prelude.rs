@@ -0,0 +1,99 @@
+use crate::encoding::{decode, encode, InvalidEncoding};
+use crate::StealthAddressOnCurve;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+create_exception!(
+    stealth_address_kit,
+    InvalidEncodingError,
+    PyValueError,
+    "Raised when bytes are not a canonical encoding of a key or point on the curve."
+);
+
+pub(crate) fn to_py_err(err: InvalidEncoding) -> PyErr {
+    InvalidEncodingError::new_err(err.to_string())
+}
+
+pub(crate) fn to_py_bytes<'py, T: CanonicalSerialize>(
+    py: Python<'py>,
+    value: &T,
+) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, &encode(value))
+}
+
+/// Decodes a compressed key, only accepting its canonical encoding.
+pub(crate) fn from_py_bytes<T: CanonicalDeserialize + CanonicalSerialize>(
+    bytes: &[u8],
+) -> PyResult<T> {
+    decode(bytes).map_err(to_py_err)
+}
+
+pub(crate) fn point_to_py_bytes<'py, C: StealthAddressOnCurve>(
+    py: Python<'py>,
+    point: &C::Projective,
+) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, &C::encode_point(point))
+}
+
+/// Decodes a point in the standard compressed encoding of the curve.
+pub(crate) fn point_from_py_bytes<C: StealthAddressOnCurve>(
+    bytes: &[u8],
+) -> PyResult<C::Projective> {
+    C::decode_point(bytes).map_err(to_py_err)
+}
+
+/// Registers a class for every enabled curve, along with the exception types,
+/// on the given python module.
+pub fn register_python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(
+        "InvalidEncodingError",
+        m.py().get_type::<InvalidEncodingError>(),
+    )?;
+
+    #[cfg(feature = "baby_jub_jub")]
+    crate::baby_jub_jub_impl::python::register(m)?;
+    #[cfg(feature = "bandersnatch")]
+    crate::bandersnatch_impl::python::register(m)?;
+    #[cfg(feature = "bls12_377")]
+    crate::bls12_377_impl::python::register(m)?;
+    #[cfg(feature = "bls12_377")]
+    crate::bls12_377_g2_impl::python::register(m)?;
+    #[cfg(feature = "bls12_381")]
+    crate::bls12_381_impl::python::register(m)?;
+    #[cfg(feature = "bls12_381")]
+    crate::bls12_381_g2_impl::python::register(m)?;
+    #[cfg(feature = "bn254")]
+    crate::bn254_impl::python::register(m)?;
+    #[cfg(feature = "bn254")]
+    crate::bn254_g2_impl::python::register(m)?;
+    #[cfg(feature = "bw6_761")]
+    crate::bw6_761_impl::python::register(m)?;
+    #[cfg(feature = "bw6_761")]
+    crate::bw6_761_g2_impl::python::register(m)?;
+    #[cfg(feature = "ed25519")]
+    crate::ed25519_impl::python::register(m)?;
+    #[cfg(feature = "ed_on_bls12_377")]
+    crate::ed_on_bls12_377_impl::python::register(m)?;
+    #[cfg(feature = "grumpkin")]
+    crate::grumpkin_impl::python::register(m)?;
+    #[cfg(feature = "jubjub")]
+    crate::jubjub_impl::python::register(m)?;
+    #[cfg(feature = "pallas")]
+    crate::pallas_impl::python::register(m)?;
+    #[cfg(feature = "ristretto255")]
+    crate::ristretto255_impl::python::register(m)?;
+    #[cfg(feature = "secp256k1")]
+    crate::secp256k1_impl::python::register(m)?;
+    #[cfg(feature = "secp256r1")]
+    crate::secp256r1_impl::python::register(m)?;
+    #[cfg(feature = "secq256k1")]
+    crate::secq256k1_impl::python::register(m)?;
+    #[cfg(feature = "stark_curve")]
+    crate::stark_curve_impl::python::register(m)?;
+    #[cfg(feature = "vesta")]
+    crate::vesta_impl::python::register(m)?;
+    Ok(())
+}
@@ -0,0 +1,473 @@
+//! The Ristretto255 group, as an arkworks curve group.
+//!
+//! Ristretto255 is the prime-order quotient of the Edwards25519 curve, so it has no cofactor to
+//! take care of. The group arithmetic, the encoding and the equality of elements are those of
+//! curve25519-dalek, which implements RFC 9496; this module only adapts them to the arkworks
+//! traits, with the scalars of ark-ed25519.
+use ark_ec::{AffineRepr, CurveConfig, CurveGroup, Group, ScalarMul, VariableBaseMSM};
+use ark_ed25519::{Fq, Fr};
+use ark_ff::{BigInteger, MontFp, PrimeField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::rand::Rng;
+use ark_std::UniformRand;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::Scalar;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use zeroize::Zeroize;
+
+/// The size of an encoded Ristretto255 element.
+const ENCODED_SIZE: usize = 32;
+
+fn to_scalar(scalar: &Fr) -> Scalar {
+    let bytes = scalar.into_bigint().to_bytes_le();
+    Scalar::from_bytes_mod_order(bytes.try_into().unwrap())
+}
+
+/// The curve parameters of Ristretto255, whose order is prime.
+pub struct Ristretto255Config;
+
+impl CurveConfig for Ristretto255Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fr = MontFp!("1");
+}
+
+/// A Ristretto255 element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RistrettoPoint(curve25519_dalek::RistrettoPoint);
+
+/// A Ristretto255 element, as the affine representation arkworks asks of a curve group. The
+/// elements have no canonical coordinates, so it is the same as [`RistrettoPoint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RistrettoAffine(curve25519_dalek::RistrettoPoint);
+
+impl RistrettoAffine {
+    /// Whether the element is a point of the group, which every decoded element is.
+    pub fn is_on_curve(&self) -> bool {
+        true
+    }
+}
+
+impl RistrettoPoint {
+    fn encode(&self) -> [u8; ENCODED_SIZE] {
+        self.0.compress().to_bytes()
+    }
+}
+
+impl From<RistrettoAffine> for RistrettoPoint {
+    fn from(point: RistrettoAffine) -> Self {
+        RistrettoPoint(point.0)
+    }
+}
+
+impl From<RistrettoPoint> for RistrettoAffine {
+    fn from(point: RistrettoPoint) -> Self {
+        RistrettoAffine(point.0)
+    }
+}
+
+impl Hash for RistrettoPoint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.encode().hash(state)
+    }
+}
+
+impl Hash for RistrettoAffine {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        RistrettoPoint::from(*self).hash(state)
+    }
+}
+
+impl Display for RistrettoPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.encode()
+            .iter()
+            .try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl Display for RistrettoAffine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        RistrettoPoint::from(*self).fmt(f)
+    }
+}
+
+impl Zeroize for RistrettoPoint {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Zeroize for RistrettoAffine {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl UniformRand for RistrettoPoint {
+    fn rand<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 64];
+        rng.fill_bytes(&mut bytes);
+        RistrettoPoint(curve25519_dalek::RistrettoPoint::from_uniform_bytes(&bytes))
+    }
+}
+
+impl UniformRand for RistrettoAffine {
+    fn rand<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        RistrettoPoint::rand(rng).into()
+    }
+}
+
+impl Valid for RistrettoPoint {
+    // every decoded element is valid
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl Valid for RistrettoAffine {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalSerialize for RistrettoPoint {
+    // there is a single, compressed, encoding
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        _compress: Compress,
+    ) -> Result<(), SerializationError> {
+        writer.write_all(&self.encode())?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: Compress) -> usize {
+        ENCODED_SIZE
+    }
+}
+
+impl CanonicalSerialize for RistrettoAffine {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        RistrettoPoint::from(*self).serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, _compress: Compress) -> usize {
+        ENCODED_SIZE
+    }
+}
+
+impl CanonicalDeserialize for RistrettoPoint {
+    // decompression rejects the non-canonical encodings
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        _compress: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut bytes = [0u8; ENCODED_SIZE];
+        reader.read_exact(&mut bytes)?;
+        CompressedRistretto(bytes)
+            .decompress()
+            .map(RistrettoPoint)
+            .ok_or(SerializationError::InvalidData)
+    }
+}
+
+impl CanonicalDeserialize for RistrettoAffine {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        RistrettoPoint::deserialize_with_mode(reader, compress, validate).map(Into::into)
+    }
+}
+
+impl Zero for RistrettoPoint {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+impl Neg for RistrettoPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        RistrettoPoint(-self.0)
+    }
+}
+
+impl Neg for RistrettoAffine {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        RistrettoAffine(-self.0)
+    }
+}
+
+/// Implements an operator for a Ristretto255 type, by value and by reference, through the
+/// curve25519-dalek elements of its operands.
+macro_rules! impl_op {
+    ($Op:ident, $op:ident, $Lhs:ty, $Rhs:ty, |$lhs:ident, $rhs:ident| $body:expr) => {
+        impl $Op<$Rhs> for $Lhs {
+            type Output = RistrettoPoint;
+
+            fn $op(self, $rhs: $Rhs) -> RistrettoPoint {
+                let $lhs = self;
+                $body
+            }
+        }
+
+        impl<'a> $Op<&'a $Rhs> for $Lhs {
+            type Output = RistrettoPoint;
+
+            fn $op(self, $rhs: &'a $Rhs) -> RistrettoPoint {
+                let $lhs = self;
+                let $rhs = *$rhs;
+                $body
+            }
+        }
+    };
+}
+
+macro_rules! impl_op_assign {
+    ($OpAssign:ident, $op_assign:ident, $Rhs:ty, $op:tt) => {
+        impl $OpAssign<$Rhs> for RistrettoPoint {
+            fn $op_assign(&mut self, rhs: $Rhs) {
+                *self = *self $op rhs;
+            }
+        }
+
+        impl<'a> $OpAssign<&'a $Rhs> for RistrettoPoint {
+            fn $op_assign(&mut self, rhs: &'a $Rhs) {
+                *self = *self $op *rhs;
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, RistrettoPoint, RistrettoPoint, |lhs, rhs| {
+    RistrettoPoint(lhs.0 + rhs.0)
+});
+impl_op!(Sub, sub, RistrettoPoint, RistrettoPoint, |lhs, rhs| {
+    RistrettoPoint(lhs.0 - rhs.0)
+});
+impl_op!(Add, add, RistrettoPoint, RistrettoAffine, |lhs, rhs| {
+    RistrettoPoint(lhs.0 + rhs.0)
+});
+impl_op!(Sub, sub, RistrettoPoint, RistrettoAffine, |lhs, rhs| {
+    RistrettoPoint(lhs.0 - rhs.0)
+});
+impl_op!(Mul, mul, RistrettoPoint, Fr, |lhs, rhs| RistrettoPoint(
+    lhs.0 * to_scalar(&rhs)
+));
+impl_op!(Add, add, RistrettoAffine, RistrettoAffine, |lhs, rhs| {
+    RistrettoPoint(lhs.0 + rhs.0)
+});
+impl_op!(Add, add, RistrettoAffine, RistrettoPoint, |lhs, rhs| rhs
+    + lhs);
+impl_op!(Mul, mul, RistrettoAffine, Fr, |lhs, rhs| RistrettoPoint(
+    lhs.0 * to_scalar(&rhs)
+));
+impl_op_assign!(AddAssign, add_assign, RistrettoPoint, +);
+impl_op_assign!(SubAssign, sub_assign, RistrettoPoint, -);
+impl_op_assign!(AddAssign, add_assign, RistrettoAffine, +);
+impl_op_assign!(SubAssign, sub_assign, RistrettoAffine, -);
+impl_op_assign!(MulAssign, mul_assign, Fr, *);
+
+impl Sum<RistrettoPoint> for RistrettoPoint {
+    fn sum<I: Iterator<Item = RistrettoPoint>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a RistrettoPoint> for RistrettoPoint {
+    fn sum<I: Iterator<Item = &'a RistrettoPoint>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl Sum<RistrettoAffine> for RistrettoPoint {
+    fn sum<I: Iterator<Item = RistrettoAffine>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a RistrettoAffine> for RistrettoPoint {
+    fn sum<I: Iterator<Item = &'a RistrettoAffine>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl Group for RistrettoPoint {
+    type ScalarField = Fr;
+
+    fn generator() -> Self {
+        RistrettoPoint(RISTRETTO_BASEPOINT_POINT)
+    }
+
+    fn double_in_place(&mut self) -> &mut Self {
+        self.0 += self.0;
+        self
+    }
+
+    // the group has prime order, so the integer is reduced by it
+    fn mul_bigint(&self, other: impl AsRef<[u64]>) -> Self {
+        let bytes: Vec<u8> = other
+            .as_ref()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect();
+        *self * Fr::from_le_bytes_mod_order(&bytes)
+    }
+}
+
+impl CurveGroup for RistrettoPoint {
+    type Config = Ristretto255Config;
+    type BaseField = Fq;
+    type Affine = RistrettoAffine;
+    type FullGroup = ();
+
+    fn normalize_batch(v: &[Self]) -> Vec<RistrettoAffine> {
+        v.iter().copied().map(Into::into).collect()
+    }
+}
+
+impl ScalarMul for RistrettoPoint {
+    type MulBase = RistrettoAffine;
+    const NEGATION_IS_CHEAP: bool = true;
+
+    fn batch_convert_to_mul_base(bases: &[Self]) -> Vec<RistrettoAffine> {
+        Self::normalize_batch(bases)
+    }
+}
+
+impl VariableBaseMSM for RistrettoPoint {}
+
+impl AffineRepr for RistrettoAffine {
+    type Config = Ristretto255Config;
+    type ScalarField = Fr;
+    type BaseField = Fq;
+    type Group = RistrettoPoint;
+
+    // the elements have no canonical coordinates, only their encoding
+    fn xy(&self) -> Option<(&Fq, &Fq)> {
+        None
+    }
+
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn generator() -> Self {
+        RistrettoPoint::generator().into()
+    }
+
+    /// Maps 64 uniformly random bytes to an element, with the one-way map of RFC 9496.
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..64)?.try_into().ok()?;
+        Some(RistrettoAffine(
+            curve25519_dalek::RistrettoPoint::from_uniform_bytes(bytes),
+        ))
+    }
+
+    fn mul_bigint(&self, by: impl AsRef<[u64]>) -> RistrettoPoint {
+        RistrettoPoint::from(*self).mul_bigint(by)
+    }
+
+    fn clear_cofactor(&self) -> Self {
+        *self
+    }
+
+    fn mul_by_cofactor_to_group(&self) -> RistrettoPoint {
+        (*self).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the encodings of the first multiples of the generator, from RFC 9496 appendix A.1
+    const MULTIPLES_OF_GENERATOR: [&str; 6] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+        "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+    ];
+
+    // invalid encodings, from RFC 9496 appendix A.2
+    const BAD_ENCODINGS: [&str; 4] = [
+        // non-canonical field encodings
+        "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        // negative field elements
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        // non-square x^2
+        "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+    ];
+
+    fn from_hex(hex: &str) -> [u8; ENCODED_SIZE] {
+        let mut bytes = [0u8; ENCODED_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn encodes_multiples_of_generator() {
+        let mut point = RistrettoPoint::zero();
+        for (i, expected) in MULTIPLES_OF_GENERATOR.into_iter().enumerate() {
+            let mut bytes = Vec::new();
+            point.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes, from_hex(expected));
+            assert_eq!(
+                RistrettoPoint::deserialize_compressed(bytes.as_slice()).unwrap(),
+                point
+            );
+            assert_eq!(RistrettoPoint::generator() * Fr::from(i as u8), point);
+            point += RistrettoPoint::generator();
+        }
+    }
+
+    #[test]
+    fn rejects_bad_encodings() {
+        for encoding in BAD_ENCODINGS {
+            assert!(RistrettoPoint::deserialize_compressed(from_hex(encoding).as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn multiplies_by_the_order_to_zero() {
+        let point = RistrettoPoint::rand(&mut ark_std::test_rng());
+        assert!(point.mul_bigint(Fr::MODULUS).is_zero());
+        assert_eq!(
+            point.mul_bigint([2]),
+            *RistrettoPoint::zero().double_in_place() + point + point
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    define_curve_tests, ristretto::RistrettoPoint, stealth_addresses::StealthAddressOnCurve,
+};
+/// Implementation of the StealthAddressOnCurve trait for the Ristretto255 group.
+use ark_ed25519::Fr;
+
+pub struct Ristretto255;
+
+impl StealthAddressOnCurve for Ristretto255 {
+    type Projective = RistrettoPoint;
+    const NAME: &'static str = "ristretto255";
+    // there is a single, compressed, encoding
+    const COMPRESSED_POINT_SIZE: usize = 32;
+    const UNCOMPRESSED_POINT_SIZE: usize = 32;
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(ristretto255, Ristretto255, Fr, RistrettoPoint, 32, 32);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Ristretto255, Fr, RistrettoPoint);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Ristretto255, Fr, RistrettoPoint);
+define_curve_tests!(Ristretto255);
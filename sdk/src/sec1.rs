@@ -0,0 +1,118 @@
+//! The SEC1 point encodings, in which secp256k1 and secp256r1 keys are exchanged: the big-endian
+//! x coordinate prefixed by `02` or `03` after the parity of y, or both coordinates prefixed by
+//! `04` when uncompressed. The point at infinity is the single byte `00` in either form.
+use crate::encoding::InvalidEncoding;
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField, Zero};
+
+const EVEN_Y: u8 = 0x02;
+const ODD_Y: u8 = 0x03;
+const UNCOMPRESSED: u8 = 0x04;
+const INFINITY: u8 = 0x00;
+
+fn coordinate_size<P: SWCurveConfig>() -> usize
+where
+    P::BaseField: PrimeField,
+{
+    P::BaseField::MODULUS_BIT_SIZE.div_ceil(8) as usize
+}
+
+fn encoded_size<P: SWCurveConfig>(compressed: bool) -> usize
+where
+    P::BaseField: PrimeField,
+{
+    if compressed {
+        1 + coordinate_size::<P>()
+    } else {
+        1 + 2 * coordinate_size::<P>()
+    }
+}
+
+fn write_coordinate<F: PrimeField>(bytes: &mut Vec<u8>, value: &F, size: usize) {
+    let be = value.into_bigint().to_bytes_be();
+    bytes.extend_from_slice(&be[be.len() - size..]);
+}
+
+/// Reads a big-endian coordinate, rejecting values that are not reduced.
+fn read_coordinate<F: PrimeField>(bytes: &[u8]) -> Result<F, InvalidEncoding> {
+    let value = F::from_be_bytes_mod_order(bytes);
+    let be = value.into_bigint().to_bytes_be();
+    if be[be.len() - bytes.len()..] != *bytes {
+        return Err(InvalidEncoding);
+    }
+    Ok(value)
+}
+
+/// Encodes a point in SEC1 format.
+pub(crate) fn encode<P: SWCurveConfig>(point: &Projective<P>, compressed: bool) -> Vec<u8>
+where
+    P::BaseField: PrimeField,
+{
+    let affine = point.into_affine();
+    if affine.infinity {
+        return vec![INFINITY];
+    }
+
+    let mut bytes = Vec::with_capacity(encoded_size::<P>(compressed));
+    let coordinate_size = coordinate_size::<P>();
+    if compressed {
+        let is_odd = affine.y.into_bigint().is_odd();
+        bytes.push(if is_odd { ODD_Y } else { EVEN_Y });
+        write_coordinate(&mut bytes, &affine.x, coordinate_size);
+    } else {
+        bytes.push(UNCOMPRESSED);
+        write_coordinate(&mut bytes, &affine.x, coordinate_size);
+        write_coordinate(&mut bytes, &affine.y, coordinate_size);
+    }
+    bytes
+}
+
+/// Decodes a point in SEC1 format, rejecting the hybrid form, unreduced coordinates, and points
+/// that are not in the prime order subgroup.
+pub(crate) fn decode<P: SWCurveConfig>(
+    bytes: &[u8],
+    compressed: bool,
+) -> Result<Projective<P>, InvalidEncoding>
+where
+    P::BaseField: PrimeField,
+{
+    if bytes == [INFINITY] {
+        return Ok(Projective::zero());
+    }
+    if bytes.len() != encoded_size::<P>(compressed) {
+        return Err(InvalidEncoding);
+    }
+    let (prefix, coordinates) = bytes.split_first().ok_or(InvalidEncoding)?;
+    let (x, y) = coordinates.split_at(coordinate_size::<P>());
+
+    let point = match (*prefix, compressed) {
+        (EVEN_Y | ODD_Y, true) => {
+            let x = read_coordinate(x)?;
+            let (y, neg_y) = Affine::<P>::get_ys_from_x_unchecked(x).ok_or(InvalidEncoding)?;
+            let is_odd = *prefix == ODD_Y;
+            let y = if y.into_bigint().is_odd() == is_odd {
+                y
+            } else {
+                neg_y
+            };
+            if y.into_bigint().is_odd() != is_odd {
+                // y is zero, which has no odd representation
+                return Err(InvalidEncoding);
+            }
+            Affine::new_unchecked(x, y)
+        }
+        (UNCOMPRESSED, false) => {
+            let point = Affine::new_unchecked(read_coordinate(x)?, read_coordinate(y)?);
+            if !point.is_on_curve() {
+                return Err(InvalidEncoding);
+            }
+            point
+        }
+        _ => return Err(InvalidEncoding),
+    };
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(InvalidEncoding);
+    }
+    Ok(point.into())
+}
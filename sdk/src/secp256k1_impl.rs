@@ -1,15 +1,147 @@
-use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
-/// Implementation of the StealthAddressOnCurve trait for the Secp256k1 curve.
+use crate::{
+    define_curve_tests, encoding::InvalidEncoding, sec1, stealth_addresses::StealthAddressOnCurve,
+};
+/// Implementation of the StealthAddressOnCurve trait for the Secp256k1 curve, which derives
+/// stealth addresses as ERC-5564 scheme 1 does.
+use ark_ff::PrimeField;
 use ark_secp256k1::{Fr, Projective};
+use tiny_keccak::{Hasher, Keccak};
 
 pub struct Secp256k1;
 
+/// The Keccak-256 hash of the shared point in compressed SEC1 format, which ERC-5564 scheme 1
+/// calls the hashed shared secret.
+fn hash_shared_secret(shared_point: &Projective) -> [u8; 32] {
+    let mut hash = [0; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(&Secp256k1::encode_point(shared_point));
+    hasher.finalize(&mut hash);
+    hash
+}
+
 impl StealthAddressOnCurve for Secp256k1 {
     type Projective = Projective;
+    const NAME: &'static str = "secp256k1";
+    // scheme 1 is secp256k1 with view tags
+    const SCHEME_ID: Option<u64> = Some(1);
+
+    /// Encodes the point in compressed SEC1 format.
+    fn encode_point(point: &Projective) -> Vec<u8> {
+        sec1::encode(point, true)
+    }
+
+    fn decode_point(bytes: &[u8]) -> Result<Projective, InvalidEncoding> {
+        sec1::decode(bytes, true)
+    }
+
+    /// Encodes the point in uncompressed SEC1 format.
+    fn encode_point_uncompressed(point: &Projective) -> Vec<u8> {
+        sec1::encode(point, false)
+    }
+
+    fn decode_point_uncompressed(bytes: &[u8]) -> Result<Projective, InvalidEncoding> {
+        sec1::decode(bytes, false)
+    }
+
+    /// Reads the hashed shared secret as a big-endian integer, reduced by the order of the curve.
+    fn hash_shared_point(shared_point: &Projective) -> Fr {
+        Fr::from_be_bytes_mod_order(&hash_shared_secret(shared_point))
+    }
+
+    /// The most significant byte of the hashed shared secret.
+    fn metadata_view_tag(shared_point: &Projective) -> u8 {
+        hash_shared_secret(shared_point)[0]
+    }
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(secp256k1, Secp256k1, Fr, Projective, 32, 33);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Secp256k1, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Secp256k1, Fr, Projective);
 define_curve_tests!(Secp256k1);
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use ark_ec::Group;
+    use ark_ff::{BigInteger, PrimeField, Zero};
+
+    const GENERATOR_X: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const GENERATOR_Y: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn encodes_points_like_sec1() {
+        let generator = Projective::generator();
+        let compressed = hex::decode(format!("02{GENERATOR_X}")).unwrap();
+        let uncompressed = hex::decode(format!("04{GENERATOR_X}{GENERATOR_Y}")).unwrap();
+
+        assert_eq!(Secp256k1::encode_point(&generator), compressed);
+        assert_eq!(Secp256k1::decode_point(&compressed), Ok(generator));
+        assert_eq!(
+            Secp256k1::encode_point_uncompressed(&generator),
+            uncompressed
+        );
+        assert_eq!(
+            Secp256k1::decode_point_uncompressed(&uncompressed),
+            Ok(generator)
+        );
+        assert_eq!(
+            Secp256k1::decode_point(&Secp256k1::encode_point(&-generator)),
+            Ok(-generator)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_point_encodings() {
+        let generator = Secp256k1::encode_point(&Projective::generator());
+        let uncompressed = Secp256k1::encode_point_uncompressed(&Projective::generator());
+
+        // the compressed and uncompressed forms are not interchangeable
+        assert!(Secp256k1::decode_point(&uncompressed).is_err());
+        assert!(Secp256k1::decode_point_uncompressed(&generator).is_err());
+
+        // the hybrid form
+        let mut hybrid = uncompressed.clone();
+        hybrid[0] = 0x06;
+        assert!(Secp256k1::decode_point_uncompressed(&hybrid).is_err());
+
+        // a point off the curve
+        let mut off_curve = uncompressed;
+        *off_curve.last_mut().unwrap() ^= 1;
+        assert!(Secp256k1::decode_point_uncompressed(&off_curve).is_err());
+
+        // x = p, which is not canonical
+        let mut non_canonical = vec![0x02];
+        non_canonical.extend(ark_secp256k1::Fq::MODULUS.to_bytes_be());
+        assert!(Secp256k1::decode_point(&non_canonical).is_err());
+
+        assert!(Secp256k1::decode_point(&generator[1..]).is_err());
+        assert!(Secp256k1::decode_point(&[]).is_err());
+    }
+
+    #[test]
+    fn encodes_the_point_at_infinity_as_one_byte() {
+        assert_eq!(Secp256k1::encode_point(&Projective::zero()), [0]);
+        assert_eq!(
+            Secp256k1::encode_point_uncompressed(&Projective::zero()),
+            [0]
+        );
+        assert_eq!(Secp256k1::decode_point(&[0]), Ok(Projective::zero()));
+        assert_eq!(
+            Secp256k1::decode_point_uncompressed(&[0]),
+            Ok(Projective::zero())
+        );
+
+        // padded with zeros to the size of the other points
+        let padded = vec![0u8; Secp256k1::COMPRESSED_POINT_SIZE];
+        assert!(Secp256k1::decode_point(&padded).is_err());
+    }
+}
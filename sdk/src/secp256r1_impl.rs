@@ -1,4 +1,6 @@
-use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+use crate::{
+    define_curve_tests, encoding::InvalidEncoding, sec1, stealth_addresses::StealthAddressOnCurve,
+};
 /// Implementation of the StealthAddressOnCurve trait for the Secp256r1 curve.
 use ark_secp256r1::{Fr, Projective};
 
@@ -6,10 +8,115 @@ pub struct Secp256r1;
 
 impl StealthAddressOnCurve for Secp256r1 {
     type Projective = Projective;
+    const NAME: &'static str = "secp256r1";
+
+    /// Encodes the point in compressed SEC1 format.
+    fn encode_point(point: &Projective) -> Vec<u8> {
+        sec1::encode(point, true)
+    }
+
+    fn decode_point(bytes: &[u8]) -> Result<Projective, InvalidEncoding> {
+        sec1::decode(bytes, true)
+    }
+
+    /// Encodes the point in uncompressed SEC1 format.
+    fn encode_point_uncompressed(point: &Projective) -> Vec<u8> {
+        sec1::encode(point, false)
+    }
+
+    fn decode_point_uncompressed(bytes: &[u8]) -> Result<Projective, InvalidEncoding> {
+        sec1::decode(bytes, false)
+    }
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(secp256r1, Secp256r1, Fr, Projective, 32, 33);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Secp256r1, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Secp256r1, Fr, Projective);
 define_curve_tests!(Secp256r1);
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use ark_ec::Group;
+    use ark_ff::{BigInteger, PrimeField, Zero};
+
+    const GENERATOR_X: &str = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+    const GENERATOR_Y: &str = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5";
+
+    #[test]
+    fn encodes_points_like_sec1() {
+        let generator = Projective::generator();
+        let compressed = hex::decode(format!("03{GENERATOR_X}")).unwrap();
+        let uncompressed = hex::decode(format!("04{GENERATOR_X}{GENERATOR_Y}")).unwrap();
+
+        assert_eq!(Secp256r1::encode_point(&generator), compressed);
+        assert_eq!(Secp256r1::decode_point(&compressed), Ok(generator));
+        assert_eq!(
+            Secp256r1::encode_point_uncompressed(&generator),
+            uncompressed
+        );
+        assert_eq!(
+            Secp256r1::decode_point_uncompressed(&uncompressed),
+            Ok(generator)
+        );
+        assert_eq!(
+            Secp256r1::decode_point(&Secp256r1::encode_point(&-generator)),
+            Ok(-generator)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_point_encodings() {
+        let generator = Secp256r1::encode_point(&Projective::generator());
+        let uncompressed = Secp256r1::encode_point_uncompressed(&Projective::generator());
+
+        // the compressed and uncompressed forms are not interchangeable
+        assert!(Secp256r1::decode_point(&uncompressed).is_err());
+        assert!(Secp256r1::decode_point_uncompressed(&generator).is_err());
+
+        // the hybrid form
+        let mut hybrid = uncompressed.clone();
+        hybrid[0] = 0x06;
+        assert!(Secp256r1::decode_point_uncompressed(&hybrid).is_err());
+
+        // a point off the curve
+        let mut off_curve = uncompressed;
+        *off_curve.last_mut().unwrap() ^= 1;
+        assert!(Secp256r1::decode_point_uncompressed(&off_curve).is_err());
+
+        // x = p, which is not canonical
+        let mut non_canonical = vec![0x02];
+        non_canonical.extend(ark_secp256r1::Fq::MODULUS.to_bytes_be());
+        assert!(Secp256r1::decode_point(&non_canonical).is_err());
+
+        assert!(Secp256r1::decode_point(&generator[1..]).is_err());
+        assert!(Secp256r1::decode_point(&[]).is_err());
+    }
+
+    #[test]
+    fn encodes_the_point_at_infinity_as_one_byte() {
+        assert_eq!(Secp256r1::encode_point(&Projective::zero()), [0]);
+        assert_eq!(
+            Secp256r1::encode_point_uncompressed(&Projective::zero()),
+            [0]
+        );
+        assert_eq!(Secp256r1::decode_point(&[0]), Ok(Projective::zero()));
+        assert_eq!(
+            Secp256r1::decode_point_uncompressed(&[0]),
+            Ok(Projective::zero())
+        );
+
+        // padded with zeros to the size of the other points
+        let padded = vec![0u8; Secp256r1::COMPRESSED_POINT_SIZE];
+        assert!(Secp256r1::decode_point(&padded).is_err());
+    }
+}
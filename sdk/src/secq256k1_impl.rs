@@ -0,0 +1,25 @@
+use crate::{define_curve_tests, stealth_addresses::StealthAddressOnCurve};
+/// Implementation of the StealthAddressOnCurve trait for the secq256k1 curve, whose base field
+/// is the scalar field of secp256k1.
+use ark_secq256k1::{Fr, Projective};
+
+pub struct Secq256k1;
+
+impl StealthAddressOnCurve for Secq256k1 {
+    type Projective = Projective;
+    const NAME: &'static str = "secq256k1";
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+define_curve_ffi!(secq256k1, Secq256k1, Fr, Projective, 32, 33);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Secq256k1, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Secq256k1, Fr, Projective);
+define_curve_tests!(Secq256k1);
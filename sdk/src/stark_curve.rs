@@ -0,0 +1,73 @@
+//! The STARK curve of Starknet, y^2 = x^3 + x + beta over the field of Starknet's felts, whose
+//! modulus is 2^251 + 17 * 2^192 + 1.
+//!
+//! There is no arkworks release of it, so it is defined here, fields included, with the
+//! parameters of starknet-rs.
+// the field derives implement their traits inside of functions, behind arkworks' `asm` feature
+#![allow(non_local_definitions, unexpected_cfgs)]
+
+use ark_ec::{
+    models::CurveConfig,
+    short_weierstrass::{self, SWCurveConfig},
+};
+use ark_ff::{Fp256, MontBackend, MontConfig, MontFp};
+
+/// The field of felts, over which the curve is defined.
+#[derive(MontConfig)]
+#[modulus = "3618502788666131213697322783095070105623107215331596699973092056135872020481"]
+#[generator = "3"]
+pub struct FqConfig;
+pub type Fq = Fp256<MontBackend<FqConfig, 4>>;
+
+/// The scalar field, of the order of the curve. 3 is a quadratic non-residue, which is all
+/// arkworks derives from the generator, as the two-adicity of the field is 1.
+#[derive(MontConfig)]
+#[modulus = "3618502788666131213697322783095070105526743751716087489154079457884512865583"]
+#[generator = "3"]
+pub struct FrConfig;
+pub type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+pub type Affine = short_weierstrass::Affine<StarkCurveConfig>;
+pub type Projective = short_weierstrass::Projective<StarkCurveConfig>;
+
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct StarkCurveConfig;
+
+impl CurveConfig for StarkCurveConfig {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fr = MontFp!("1");
+}
+
+impl SWCurveConfig for StarkCurveConfig {
+    const COEFF_A: Fq = MontFp!("1");
+    /// The digits of pi, as starknet-rs's `BETA`.
+    const COEFF_B: Fq =
+        MontFp!("3141592653589793238462643383279502884197169399375105820974944592307816406665");
+    const GENERATOR: Affine = Affine::new_unchecked(
+        MontFp!("874739451078007766457464989774322083649278607533249481151382481072868806602"),
+        MontFp!("152666792071518830868575557812948353041420400780739481342941381225525861407"),
+    );
+
+    #[inline(always)]
+    fn mul_by_a(element: Fq) -> Fq {
+        element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{PrimeField, Zero};
+
+    #[test]
+    fn generator_has_the_order_of_the_scalar_field() {
+        let generator = Affine::generator();
+        assert!(generator.is_on_curve());
+        assert!(generator.mul_bigint(Fr::MODULUS).is_zero());
+        assert!(!(generator * Fr::from(2u8)).into_affine().is_zero());
+    }
+}
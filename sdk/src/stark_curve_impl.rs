@@ -0,0 +1,140 @@
+use crate::{
+    define_curve_tests,
+    stark_curve::{Fr, Projective},
+    starknet::{from_felt, to_felt},
+    stealth_addresses::StealthAddressOnCurve,
+};
+/// Implementation of the StealthAddressOnCurve trait for the STARK curve of Starknet, whose
+/// shared points are hashed with Starknet's Poseidon hash.
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use starknet_crypto::poseidon_hash;
+
+pub struct StarkCurve;
+
+impl StealthAddressOnCurve for StarkCurve {
+    type Projective = Projective;
+    const NAME: &'static str = "stark_curve";
+
+    /// Hashes the coordinates of the shared point with Poseidon, as a Cairo contract can, and
+    /// reduces the felt by the order of the curve.
+    fn hash_shared_point(shared_point: &Projective) -> Fr {
+        let shared_point = shared_point.into_affine();
+        let hash = from_felt(&poseidon_hash(
+            to_felt(&shared_point.x),
+            to_felt(&shared_point.y),
+        ));
+        Fr::from_be_bytes_mod_order(&hash.into_bigint().to_bytes_be())
+    }
+}
+
+#[cfg(feature = "ffi")]
+use crate::define_curve_ffi;
+#[cfg(feature = "ffi")]
+use crate::starknet::{felt_from_bytes, felt_to_bytes, AccountClass};
+#[cfg(feature = "ffi")]
+define_curve_ffi!(stark_curve, StarkCurve, Fr, Projective, 32, 32);
+
+/// A felt, as 32 big-endian bytes.
+#[cfg(feature = "ffi")]
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug)]
+pub struct stark_curveFelt([u8; 32]);
+
+/// The address of the Starknet account of the given class that a stealth public key controls.
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn stark_curve_ffi_account_address(
+    class_hash: *mut stark_curveFelt,
+    stealth_public_key: *mut stark_curveProjective,
+) -> *mut CReturn<stark_curveFelt> {
+    let invalid = || {
+        Box::into_raw(Box::new(CReturn {
+            value: stark_curveFelt([0u8; 32]),
+            err_code: CErrorCode::InvalidKeys,
+        }))
+    };
+    if class_hash.is_null() || stealth_public_key.is_null() {
+        return invalid();
+    }
+    let Some(class_hash) = felt_from_bytes(&unsafe { &*class_hash }.0) else {
+        return invalid();
+    };
+    let stealth_public_key: Projective = match unsafe { &*stealth_public_key }.try_into() {
+        Ok(v) => v,
+        Err(_) => return invalid(),
+    };
+
+    let address = AccountClass::new(class_hash).account_address(&stealth_public_key);
+    Box::into_raw(Box::new(CReturn {
+        value: stark_curveFelt(felt_to_bytes(&address)),
+        err_code: CErrorCode::NoError,
+    }))
+}
+
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub extern "C" fn drop_stark_curve_ffi_account_address(ptr: *mut CReturn<stark_curveFelt>) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe {
+        let _ = Box::from_raw(ptr);
+    }
+}
+
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(StarkCurve, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(StarkCurve, Fr, Projective);
+define_curve_tests!(StarkCurve);
+
+#[cfg(test)]
+mod poseidon_tests {
+    use super::*;
+    use ark_ec::Group;
+
+    #[test]
+    fn hashes_shared_points_with_poseidon() {
+        let shared_point = Projective::generator() * Fr::from(5u8);
+        let affine = shared_point.into_affine();
+        let hash = poseidon_hash(to_felt(&affine.x), to_felt(&affine.y));
+        assert_eq!(
+            StarkCurve::hash_shared_point(&shared_point),
+            Fr::from_be_bytes_mod_order(&hash.to_bytes_be())
+        );
+        assert_ne!(
+            StarkCurve::hash_shared_point(&shared_point),
+            StarkCurve::hash_to_fr(&StarkCurve::encode_point(&shared_point))
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn ffi_account_address_matches_rust() {
+        let (_, public_key) = StarkCurve::random_keypair();
+        let class_hash = crate::starknet::Felt::from(7u8);
+        let mut ffi_class_hash = stark_curveFelt(felt_to_bytes(&class_hash));
+        let mut ffi_public_key = stark_curveProjective::try_from(public_key).unwrap();
+
+        let result = stark_curve_ffi_account_address(&mut ffi_class_hash, &mut ffi_public_key);
+        let address = unsafe { &*result };
+        assert_eq!(address.err_code, CErrorCode::NoError);
+        assert_eq!(
+            address.value.0,
+            felt_to_bytes(&AccountClass::new(class_hash).account_address(&public_key))
+        );
+        drop_stark_curve_ffi_account_address(result);
+
+        // a class hash that is not a felt
+        let mut out_of_range = stark_curveFelt([0xff; 32]);
+        let result = stark_curve_ffi_account_address(&mut out_of_range, &mut ffi_public_key);
+        assert_eq!(unsafe { &*result }.err_code, CErrorCode::InvalidKeys);
+        drop_stark_curve_ffi_account_address(result);
+    }
+}
@@ -0,0 +1,219 @@
+//! Starknet accounts as stealth destinations: the address of the account contract that a stealth
+//! public key on the STARK curve controls, computed as Starknet computes contract addresses, so
+//! that a payment can be sent to the account before it is deployed.
+//!
+//! A Starknet public key is the x coordinate of the point, so the account takes the x coordinate
+//! of the stealth public key, and the recipient deploys and controls it with the stealth private
+//! key, as a Starknet private key.
+//!
+//! ```
+//! use stealth_address_kit::prelude::*;
+//! use stealth_address_kit::starknet::{felt_from_hex, felt_to_hex, AccountClass};
+//!
+//! let account_class = AccountClass::new(
+//!     felt_from_hex("0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f").unwrap(),
+//! );
+//! let (viewing_key, viewing_public_key) = StarkCurve::random_keypair();
+//! let (spending_key, spending_public_key) = StarkCurve::random_keypair();
+//! let (ephemeral_private_key, ephemeral_public_key) = StarkCurve::random_keypair();
+//!
+//! // the sender pays into the account of the stealth public key
+//! let (stealth_public_key, view_tag) = StarkCurve::generate_stealth_address(
+//!     viewing_public_key,
+//!     spending_public_key,
+//!     ephemeral_private_key,
+//! );
+//! let account = account_class.account_address(&stealth_public_key);
+//!
+//! // the recipient finds the same account
+//! let stealth_private_key = StarkCurve::generate_stealth_private_key(
+//!     ephemeral_public_key,
+//!     viewing_key,
+//!     spending_key,
+//!     view_tag,
+//! )
+//! .unwrap();
+//! let stealth_public_key = StarkCurve::derive_public_key(&stealth_private_key);
+//! assert_eq!(account_class.account_address(&stealth_public_key), account);
+//! println!("{}", felt_to_hex(&account));
+//! ```
+use crate::prelude::PublicKey;
+use crate::stark_curve::Fq;
+use crate::StarkCurve;
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, Field, PrimeField};
+use starknet_crypto::{pedersen_hash, FieldElement};
+
+/// A field element of Starknet, which is the base field of the STARK curve.
+pub type Felt = Fq;
+
+/// The prefix of the hash of a contract address, as a felt.
+const CONTRACT_ADDRESS_PREFIX: &[u8] = b"STARKNET_CONTRACT_ADDRESS";
+
+pub(crate) fn to_felt(felt: &Felt) -> FieldElement {
+    FieldElement::from_bytes_be(&felt_to_bytes(felt)).unwrap()
+}
+
+pub(crate) fn from_felt(felt: &FieldElement) -> Felt {
+    Felt::from_be_bytes_mod_order(&felt.to_bytes_be())
+}
+
+/// Encodes a felt as 32 big-endian bytes.
+pub fn felt_to_bytes(felt: &Felt) -> [u8; 32] {
+    felt.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+/// Decodes a felt from 32 big-endian bytes, unless they are not below the modulus.
+pub fn felt_from_bytes(bytes: &[u8; 32]) -> Option<Felt> {
+    let felt = Felt::from_be_bytes_mod_order(bytes);
+    (felt_to_bytes(&felt) == *bytes).then_some(felt)
+}
+
+/// Encodes a felt as `0x` and 64 hexadecimal digits, as Starknet wallets show addresses.
+pub fn felt_to_hex(felt: &Felt) -> String {
+    let digits: String = felt_to_bytes(felt)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("0x{digits}")
+}
+
+/// Decodes a felt from `0x` and up to 64 hexadecimal digits.
+pub fn felt_from_hex(text: &str) -> Option<Felt> {
+    let digits = text.strip_prefix("0x")?;
+    if digits.is_empty() || digits.len() > 64 || !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits = format!("{digits:0>64}");
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    felt_from_bytes(&bytes)
+}
+
+/// The Pedersen hash of a list of felts, as Starknet hashes arrays: the elements are hashed in
+/// turn from zero, and the hash of the last of them with the length of the list.
+pub fn pedersen_hash_on_elements(elements: &[Felt]) -> Felt {
+    let hash = elements.iter().fold(FieldElement::ZERO, |hash, element| {
+        pedersen_hash(&hash, &to_felt(element))
+    });
+    from_felt(&pedersen_hash(&hash, &FieldElement::from(elements.len())))
+}
+
+/// The address of a contract, as Starknet computes it from the deployer, the salt, the class
+/// hash and the constructor calldata. The deployer is zero for accounts deployed by a
+/// `DEPLOY_ACCOUNT` transaction.
+pub fn contract_address(
+    deployer: &Felt,
+    salt: &Felt,
+    class_hash: &Felt,
+    calldata: &[Felt],
+) -> Felt {
+    let address = pedersen_hash_on_elements(&[
+        Felt::from_be_bytes_mod_order(CONTRACT_ADDRESS_PREFIX),
+        *deployer,
+        *salt,
+        *class_hash,
+        pedersen_hash_on_elements(calldata),
+    ]);
+    // addresses are reduced below 2^251 - 256, so that they are valid storage addresses
+    let bound = Felt::from(2u8).pow([251]) - Felt::from(256u16);
+    if address.into_bigint() >= bound.into_bigint() {
+        address - bound
+    } else {
+        address
+    }
+}
+
+/// The Starknet public key of a point, which is its x coordinate.
+pub fn public_key_felt(public_key: &PublicKey<StarkCurve>) -> Felt {
+    public_key.into_affine().x
+}
+
+/// An account class whose constructor takes the public key alone, as OpenZeppelin's account
+/// does, with the public key as the salt of the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountClass {
+    pub class_hash: Felt,
+    /// The deployer of the accounts, which is zero for `DEPLOY_ACCOUNT` transactions.
+    pub deployer: Felt,
+}
+
+impl AccountClass {
+    /// The accounts of the class deployed by `DEPLOY_ACCOUNT` transactions.
+    pub fn new(class_hash: Felt) -> Self {
+        Self {
+            class_hash,
+            deployer: Felt::from(0u8),
+        }
+    }
+
+    /// The address of the account of a public key.
+    pub fn account_address(&self, public_key: &PublicKey<StarkCurve>) -> Felt {
+        let public_key = public_key_felt(public_key);
+        contract_address(&self.deployer, &public_key, &self.class_hash, &[public_key])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stealth_addresses::StealthAddressOnCurve;
+
+    #[test]
+    fn encodes_felts_as_hex() {
+        let felt = Felt::from(0x1234u16);
+        assert_eq!(
+            felt_to_hex(&felt),
+            "0x0000000000000000000000000000000000000000000000000000000000001234"
+        );
+        assert_eq!(felt_from_hex(&felt_to_hex(&felt)), Some(felt));
+        assert_eq!(felt_from_hex("0x1234"), Some(felt));
+
+        assert_eq!(felt_from_hex("1234"), None);
+        assert_eq!(felt_from_hex("0x"), None);
+        assert_eq!(felt_from_hex("0x12g4"), None);
+        // the modulus, which is not a felt
+        assert_eq!(
+            felt_from_hex("0x0800000000000011000000000000000000000000000000000000000000000001"),
+            None
+        );
+    }
+
+    #[test]
+    fn converts_felts_to_starknet_crypto() {
+        let felt = -Felt::from(1u8);
+        assert_eq!(to_felt(&felt), FieldElement::ZERO - FieldElement::ONE);
+        assert_eq!(from_felt(&to_felt(&felt)), felt);
+    }
+
+    #[test]
+    fn hashes_arrays_as_starknet_does() {
+        let elements = [Felt::from(1u8), Felt::from(2u8)];
+        let one = FieldElement::ONE;
+        let two = FieldElement::TWO;
+        let expected = pedersen_hash(
+            &pedersen_hash(&pedersen_hash(&FieldElement::ZERO, &one), &two),
+            &two,
+        );
+        assert_eq!(pedersen_hash_on_elements(&elements), from_felt(&expected));
+    }
+
+    #[test]
+    fn accounts_depend_on_the_public_key_and_deployer() {
+        let account_class = AccountClass::new(Felt::from(7u8));
+        let (_, public_key) = StarkCurve::random_keypair();
+        let (_, other_public_key) = StarkCurve::random_keypair();
+        let address = account_class.account_address(&public_key);
+
+        let bound = Felt::from(2u8).pow([251]) - Felt::from(256u16);
+        assert!(address.into_bigint() < bound.into_bigint());
+        assert_ne!(address, account_class.account_address(&other_public_key));
+        let deployed = AccountClass {
+            deployer: Felt::from(1u8),
+            ..account_class
+        };
+        assert_ne!(address, deployed.account_address(&public_key));
+    }
+}
@@ -1,6 +1,7 @@
+use crate::encoding::{decode, decode_uncompressed, encode, encode_uncompressed, InvalidEncoding};
+use crate::metadata::{AnnouncementMetadata, Transfer};
 use ark_ec::{AffineRepr, CurveGroup, Group};
-use ark_ff::{Fp, FpConfig, PrimeField};
-use ark_serialize::CanonicalSerialize;
+use ark_ff::{Field, Fp, FpConfig, PrimeField};
 use ark_std::rand::rngs::OsRng;
 use ark_std::UniformRand;
 use std::fmt::Display;
@@ -22,27 +23,31 @@ where
     }
 }
 
-/// A trait for converting projective points to bytes.
-pub trait ToBytesFromProjective {
-    /// Converts the projective point to a byte vector.
-    fn to_bytes(&self) -> Vec<u8>;
+// we want to route through CurveGroup -> Config -> ScalarField
+type FrOf<P> =
+    <<<P as StealthAddressOnCurve>::Projective as CurveGroup>::Affine as AffineRepr>::ScalarField;
+
+type BaseFieldOf<P> =
+    <<<P as StealthAddressOnCurve>::Projective as CurveGroup>::Affine as AffineRepr>::BaseField;
+
+/// The flag bits arkworks packs into the last coordinate of a short Weierstrass point.
+const SW_FLAG_BITS: u32 = 2;
+
+const fn byte_size(bits: u32) -> usize {
+    bits.div_ceil(8) as usize
 }
 
-impl<G: CurveGroup> ToBytesFromProjective for G
-where
-    G::Affine: CanonicalSerialize,
-{
-    fn to_bytes(&self) -> Vec<u8> {
-        let affine = self.into_affine();
-        let mut bytes = Vec::with_capacity(affine.compressed_size());
-        affine.serialize_compressed(&mut bytes).unwrap();
-        bytes
-    }
+const fn base_field_bits<P: StealthAddressOnCurve + ?Sized>() -> u32 {
+    <<BaseFieldOf<P> as Field>::BasePrimeField as PrimeField>::MODULUS_BIT_SIZE
 }
 
-// we want to route through CurveGroup -> Config -> ScalarField
-type FrOf<P> =
-    <<<P as StealthAddressOnCurve>::Projective as CurveGroup>::Affine as AffineRepr>::ScalarField;
+/// The extension degree of the base field, and the size of an element of its prime field.
+const fn base_field_shape<P: StealthAddressOnCurve + ?Sized>() -> (usize, usize) {
+    // an extension field element is made of `degree` prime field elements
+    let degree = std::mem::size_of::<BaseFieldOf<P>>()
+        / std::mem::size_of::<<BaseFieldOf<P> as Field>::BasePrimeField>();
+    (degree, byte_size(base_field_bits::<P>()))
+}
 
 /// A trait for implementing stealth addresses on elliptic curves.
 pub trait StealthAddressOnCurve {
@@ -52,6 +57,68 @@ pub trait StealthAddressOnCurve {
         + From<<Self::Projective as CurveGroup>::Affine>
         + CurveGroup;
 
+    /// The name of the curve, after its cargo feature, with a `_g2` suffix for the G2 groups of
+    /// the pairing curves.
+    const NAME: &'static str;
+
+    /// The ERC-5564 scheme id of the curve, if it has one.
+    const SCHEME_ID: Option<u64> = None;
+
+    /// The size of a compressed private key.
+    const SCALAR_SIZE: usize = byte_size(<FrOf<Self> as PrimeField>::MODULUS_BIT_SIZE);
+
+    /// The size of a compressed point, as `encode_point` encodes public keys and stealth
+    /// addresses.
+    ///
+    /// Defaults to the size for a short Weierstrass curve, which other curve models override.
+    const COMPRESSED_POINT_SIZE: usize = {
+        let (degree, element_size) = base_field_shape::<Self>();
+        (degree - 1) * element_size + byte_size(base_field_bits::<Self>() + SW_FLAG_BITS)
+    };
+
+    /// The size of an uncompressed point, as `encode_point_uncompressed` encodes it.
+    ///
+    /// Defaults to the size for a short Weierstrass curve, which other curve models override.
+    const UNCOMPRESSED_POINT_SIZE: usize = {
+        let (degree, element_size) = base_field_shape::<Self>();
+        (2 * degree - 1) * element_size + byte_size(base_field_bits::<Self>() + SW_FLAG_BITS)
+    };
+
+    /// The size of a view tag, which is the least significant limb of the hashed shared point.
+    const VIEW_TAG_SIZE: usize = std::mem::size_of::<u64>();
+
+    /// Encodes a point in the standard compressed format for the curve, e.g. SEC1 for secp256k1,
+    /// which public keys and stealth addresses are exchanged in, and the shared point is hashed in.
+    ///
+    /// Defaults to the canonical compressed encoding of arkworks, for curves without a standard
+    /// format.
+    #[inline]
+    fn encode_point(point: &Self::Projective) -> Vec<u8> {
+        encode(point)
+    }
+
+    /// Decodes a point encoded by `encode_point`, rejecting non-canonical encodings and points
+    /// outside of the prime order subgroup.
+    #[inline]
+    fn decode_point(bytes: &[u8]) -> Result<Self::Projective, InvalidEncoding> {
+        decode(bytes)
+    }
+
+    /// Encodes a point in the standard uncompressed format for the curve.
+    ///
+    /// Defaults to the canonical uncompressed encoding of arkworks.
+    #[inline]
+    fn encode_point_uncompressed(point: &Self::Projective) -> Vec<u8> {
+        encode_uncompressed(point)
+    }
+
+    /// Decodes a point encoded by `encode_point_uncompressed`, rejecting non-canonical encodings
+    /// and points outside of the prime order subgroup.
+    #[inline]
+    fn decode_point_uncompressed(bytes: &[u8]) -> Result<Self::Projective, InvalidEncoding> {
+        decode_uncompressed(bytes)
+    }
+
     /// Derives a public key from a given private key.
     ///
     /// # Arguments
@@ -111,6 +178,34 @@ pub trait StealthAddressOnCurve {
         FrOf::<Self>::from_le_bytes_mod_order(hash.as_slice())
     }
 
+    /// Hashes the shared point into the scalar that offsets the spending key, whose least
+    /// significant limb is the view tag.
+    ///
+    /// Defaults to `hash_to_fr` of the point as `encode_point` encodes it, which curves with a
+    /// hash of their own override.
+    #[inline]
+    fn hash_shared_point(shared_point: &Self::Projective) -> FrOf<Self>
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        Self::hash_to_fr(&Self::encode_point(shared_point))
+    }
+
+    /// The view tag byte that the metadata of ERC-5564 announcements carries, for the shared
+    /// point.
+    ///
+    /// Defaults to the lowest byte of the view tag, which curves with a scheme of the standard
+    /// override with the byte the scheme specifies.
+    #[inline]
+    fn metadata_view_tag(shared_point: &Self::Projective) -> u8
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        Self::hash_shared_point(shared_point)
+            .get_view_tag()
+            .to_le_bytes()[0]
+    }
+
     /// Computes a shared elliptic curve point given a private key and a public key.
     ///
     /// # Arguments
@@ -150,12 +245,44 @@ pub trait StealthAddressOnCurve {
         FrOf<Self>: HasViewTag,
     {
         let q = Self::compute_shared_point(ephemeral_private_key, viewing_public_key);
-        let q_hashed = Self::hash_to_fr(&q.to_bytes());
+        let q_hashed = Self::hash_shared_point(&q);
         let q_hashed_in_g1 = Self::derive_public_key(&q_hashed);
         let view_tag = q_hashed.get_view_tag();
         (q_hashed_in_g1 + spending_public_key, view_tag)
     }
 
+    /// Generates a stealth address, along with the metadata to announce it with as ERC-5564
+    /// does, which carries the view tag byte of `metadata_view_tag`.
+    ///
+    /// # Arguments
+    ///
+    /// * `viewing_public_key` - The viewing public key.
+    /// * `spending_public_key` - The spending public key.
+    /// * `ephemeral_private_key` - The ephemeral private key.
+    /// * `transfer` - What the payment transfers, if the metadata should describe it.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the stealth address and the metadata.
+    #[inline]
+    fn generate_stealth_address_with_metadata(
+        viewing_public_key: Self::Projective,
+        spending_public_key: Self::Projective,
+        ephemeral_private_key: FrOf<Self>,
+        transfer: Option<Transfer>,
+    ) -> (Self::Projective, AnnouncementMetadata)
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        let q = Self::compute_shared_point(ephemeral_private_key, viewing_public_key);
+        let q_hashed = Self::hash_shared_point(&q);
+        let metadata = AnnouncementMetadata::new(Self::metadata_view_tag(&q), transfer);
+        (
+            Self::derive_public_key(&q_hashed) + spending_public_key,
+            metadata,
+        )
+    }
+
     /// Generates a stealth private key.
     ///
     /// # Arguments
@@ -179,11 +306,131 @@ pub trait StealthAddressOnCurve {
         FrOf<Self>: HasViewTag,
     {
         let q_receiver = Self::compute_shared_point(viewing_key, ephemeral_public_key);
-        let q_receiver_hashed = Self::hash_to_fr(&q_receiver.to_bytes());
+        let q_receiver_hashed = Self::hash_shared_point(&q_receiver);
         if q_receiver_hashed.get_view_tag() == expected_view_tag {
             Some(spending_key + q_receiver_hashed)
         } else {
             None
         }
     }
+
+    /// Checks whether an announcement is addressed to the recipient, using only the viewing key.
+    /// This allows a view-only party to detect payments without holding the spending key.
+    ///
+    /// # Arguments
+    ///
+    /// * `ephemeral_public_key` - The ephemeral public key.
+    /// * `viewing_key` - The viewing key.
+    /// * `spending_public_key` - The spending public key.
+    /// * `expected_view_tag` - The expected view tag.
+    ///
+    /// # Returns
+    ///
+    /// The stealth address the announcement must carry, if the view tag matches.
+    #[inline]
+    fn check_stealth_address(
+        ephemeral_public_key: Self::Projective,
+        viewing_key: FrOf<Self>,
+        spending_public_key: Self::Projective,
+        expected_view_tag: u64,
+    ) -> Option<Self::Projective>
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        let q_receiver = Self::compute_shared_point(viewing_key, ephemeral_public_key);
+        let q_receiver_hashed = Self::hash_shared_point(&q_receiver);
+        if q_receiver_hashed.get_view_tag() == expected_view_tag {
+            Some(Self::derive_public_key(&q_receiver_hashed) + spending_public_key)
+        } else {
+            None
+        }
+    }
+
+    /// Scans a batch of announcements for the ones addressed to the recipient, whose view tag
+    /// matches and whose stealth address is the one the keys derive. This is the scan every
+    /// binding delegates to.
+    ///
+    /// # Arguments
+    ///
+    /// * `announcements` - The stealth addresses, ephemeral public keys and view tags of the
+    ///   announcements.
+    /// * `viewing_key` - The viewing key.
+    /// * `spending_key` - The spending key.
+    ///
+    /// # Returns
+    ///
+    /// The index of every matching announcement, along with its stealth private key.
+    fn scan(
+        announcements: &[(Self::Projective, Self::Projective, u64)],
+        viewing_key: FrOf<Self>,
+        spending_key: FrOf<Self>,
+    ) -> Vec<(usize, FrOf<Self>)>
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        let spending_public_key = Self::derive_public_key(&spending_key);
+        announcements
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(index, (stealth_address, ephemeral_public_key, view_tag))| {
+                    let q_receiver = Self::compute_shared_point(viewing_key, *ephemeral_public_key);
+                    let q_receiver_hashed = Self::hash_shared_point(&q_receiver);
+                    if q_receiver_hashed.get_view_tag() != *view_tag
+                        || Self::derive_public_key(&q_receiver_hashed) + spending_public_key
+                            != *stealth_address
+                    {
+                        return None;
+                    }
+                    Some((index, spending_key + q_receiver_hashed))
+                },
+            )
+            .collect()
+    }
+
+    /// Scans a batch of announcements as ERC-5564 publishes them, whose metadata carries the view
+    /// tag byte of `metadata_view_tag`, for the ones addressed to the recipient. As one byte
+    /// matches by chance, the stealth address is checked too.
+    ///
+    /// # Arguments
+    ///
+    /// * `announcements` - The stealth addresses, ephemeral public keys and metadata of the
+    ///   announcements.
+    /// * `viewing_key` - The viewing key.
+    /// * `spending_key` - The spending key.
+    ///
+    /// # Returns
+    ///
+    /// The index of every matching announcement, along with its stealth private key and its
+    /// decoded metadata, which tells what was transferred.
+    fn scan_with_metadata(
+        announcements: &[(Self::Projective, Self::Projective, &[u8])],
+        viewing_key: FrOf<Self>,
+        spending_key: FrOf<Self>,
+    ) -> Vec<(usize, FrOf<Self>, AnnouncementMetadata)>
+    where
+        FrOf<Self>: HasViewTag,
+    {
+        let spending_public_key = Self::derive_public_key(&spending_key);
+        announcements
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(index, (stealth_address, ephemeral_public_key, metadata))| {
+                    let metadata = AnnouncementMetadata::decode(metadata).ok()?;
+                    let q_receiver = Self::compute_shared_point(viewing_key, *ephemeral_public_key);
+                    if !metadata.matches_view_tag(Self::metadata_view_tag(&q_receiver)) {
+                        return None;
+                    }
+                    let q_receiver_hashed = Self::hash_shared_point(&q_receiver);
+                    if Self::derive_public_key(&q_receiver_hashed) + spending_public_key
+                        != *stealth_address
+                    {
+                        return None;
+                    }
+                    Some((index, spending_key + q_receiver_hashed, metadata))
+                },
+            )
+            .collect()
+    }
 }
@@ -0,0 +1,45 @@
+use crate::stealth_addresses::StealthAddressOnCurve;
+use ark_ec::CurveGroup;
+use std::marker::PhantomData;
+
+/// Implements the StealthAddressOnCurve trait for any arkworks curve group, so that a curve
+/// the sdk does not ship needs no impl of its own.
+///
+/// The shared point is computed without clearing the cofactor, so curves with a cofactor
+/// should get their own impl overriding `compute_shared_point`, as `Ed25519` does. Likewise,
+/// the curve is named `custom`, its point sizes are those of a short Weierstrass curve, and its
+/// points are encoded as arkworks does rather than in a standard format such as SEC1, so that a
+/// wrapped secp256k1 does not derive the same stealth addresses as `Secp256k1`.
+///
+/// ```
+/// # #[cfg(feature = "secp256k1")]
+/// # {
+/// use stealth_address_kit::{StealthAddressOnCurve, StealthCurve};
+///
+/// type Secp256k1 = StealthCurve<ark_secp256k1::Projective>;
+///
+/// let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+/// let (viewing_key, viewing_public_key) = Secp256k1::random_keypair();
+/// let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+///
+/// let (stealth_address, view_tag) = Secp256k1::generate_stealth_address(
+///     viewing_public_key,
+///     spending_public_key,
+///     ephemeral_private_key,
+/// );
+/// let stealth_private_key = Secp256k1::generate_stealth_private_key(
+///     ephemeral_public_key,
+///     viewing_key,
+///     spending_key,
+///     view_tag,
+/// )
+/// .unwrap();
+/// assert_eq!(Secp256k1::derive_public_key(&stealth_private_key), stealth_address);
+/// # }
+/// ```
+pub struct StealthCurve<G>(PhantomData<G>);
+
+impl<G: CurveGroup> StealthAddressOnCurve for StealthCurve<G> {
+    type Projective = G;
+    const NAME: &'static str = "custom";
+}
@@ -0,0 +1,395 @@
+//! Text forms for keys and meta-addresses, so that they can be shared outside of Ethereum, and
+//! mistyped ones are caught rather than silently decoding to another key.
+//!
+//! Public keys and meta-addresses are made of points in the standard compressed encoding of their
+//! curve, as `encode_point` encodes them, with the spending public key first, and secret keys are
+//! in their canonical compressed encoding. There are three formats:
+//!
+//! - `Hex`: the bytes as 0x-prefixed hex, with a mixed-case checksum as in EIP-55, but hashing the
+//!   bech32m prefix before the digits, so that the checksum binds the kind and the curve too. It
+//!   is extended to longer strings by hashing the previous hash for every 64 hex digits, and is
+//!   required: all-lowercase and all-uppercase hex do not parse.
+//! - `Bech32m`: the bytes in bech32m, under a prefix naming the kind and the curve, e.g.
+//!   `st_secp256k1` for a secp256k1 meta-address, `pk_secp256k1` for a public key, and
+//!   `sk_secp256k1` for a secret key.
+//! - `Base58Check`: the bytes in base58check, after the first four bytes of the Keccak-256 hash of
+//!   the bech32m prefix.
+//!
+//! `Display` writes bech32m, and `FromStr` parses any of the formats:
+//!
+//! ```
+//! # #[cfg(all(feature = "secp256k1", feature = "secp256r1"))]
+//! # {
+//! use stealth_address_kit::prelude::*;
+//! use stealth_address_kit::text::TextFormat;
+//!
+//! let (_, spending_public_key) = Secp256k1::random_keypair();
+//! let (_, viewing_public_key) = Secp256k1::random_keypair();
+//! let meta_address = MetaAddress::<Secp256k1>::new(spending_public_key, viewing_public_key);
+//!
+//! let text = meta_address.to_string();
+//! assert!(text.starts_with("st_secp256k11"));
+//! assert_eq!(text.parse::<MetaAddress<Secp256k1>>().unwrap(), meta_address);
+//! assert!(text.parse::<MetaAddress<Secp256r1>>().is_err());
+//!
+//! let hex = meta_address.to_text(TextFormat::Hex);
+//! assert_eq!(hex.parse::<MetaAddress<Secp256k1>>().unwrap(), meta_address);
+//! assert!(hex.parse::<MetaAddress<Secp256r1>>().is_err());
+//! # }
+//! ```
+use crate::encoding::{decode, encode};
+use crate::meta_address::MetaAddress;
+use crate::prelude::Fr;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use bech32::primitives::decode::{CheckedHrpstring, CheckedHrpstringError, ChecksumError};
+use bech32::{Bech32m, Hrp};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The size of the tag that base58check payloads start with.
+const TAG_SIZE: usize = 4;
+
+/// A text format for keys and meta-addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextFormat {
+    /// 0x-prefixed hex with a mixed-case checksum of the kind, the curve and the bytes.
+    Hex,
+    /// Bech32m under a prefix naming the kind and the curve.
+    Bech32m,
+    /// Base58check after a tag naming the kind and the curve.
+    Base58Check,
+}
+
+/// Why a key or meta-address could not be parsed from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text is malformed for its format, e.g. it has a character outside of its alphabet.
+    Malformed(String),
+    /// The checksum does not match, e.g. after a typo.
+    InvalidChecksum,
+    /// The text is for another curve, or another kind of key. `found` is the prefix of the
+    /// text, if the format names it.
+    WrongPrefix {
+        expected: String,
+        found: Option<String>,
+    },
+    /// The text does not hold as many bytes as the kind of key does on the curve.
+    InvalidLength { expected: usize, found: usize },
+    /// The bytes are not a valid key or point on the curve.
+    InvalidEncoding,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Malformed(reason) => write!(f, "malformed text: {reason}"),
+            ParseError::InvalidChecksum => write!(f, "invalid checksum, the text may be mistyped"),
+            ParseError::WrongPrefix {
+                expected,
+                found: Some(found),
+            } => write!(f, "expected a `{expected}` string, found `{found}`"),
+            ParseError::WrongPrefix {
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "expected a `{expected}` string, found another curve or kind of key"
+            ),
+            ParseError::InvalidLength { expected, found } => {
+                write!(f, "expected {expected} bytes, found {found}")
+            }
+            ParseError::InvalidEncoding => write!(f, "not a valid key or point on the curve"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The kinds of text, which are told apart by their prefix.
+#[derive(Clone, Copy)]
+enum Kind {
+    MetaAddress,
+    PublicKey,
+    SecretKey,
+}
+
+impl Kind {
+    fn prefix<C: StealthAddressOnCurve>(self) -> String {
+        let kind = match self {
+            Kind::MetaAddress => "st",
+            Kind::PublicKey => "pk",
+            Kind::SecretKey => "sk",
+        };
+        format!("{kind}_{}", C::NAME)
+    }
+}
+
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Writes the bytes as hex, with the letters of the digits whose checksum nibble is at least 8
+/// in uppercase. The checksum hashes the prefix before the lowercase digits, which makes it
+/// EIP-55's for an empty prefix.
+fn checksummed_hex(prefix: &str, bytes: &[u8]) -> String {
+    let lowercase = hex::encode(bytes);
+    let mut hash = keccak256(format!("{prefix}{lowercase}").as_bytes());
+    let mut checksummed = String::with_capacity(2 + lowercase.len());
+    checksummed.push_str("0x");
+    for (index, digit) in lowercase.chars().enumerate() {
+        if index > 0 && index % 64 == 0 {
+            hash = keccak256(&hash);
+        }
+        let byte = hash[index % 64 / 2];
+        let nibble = if index % 2 == 0 {
+            byte >> 4
+        } else {
+            byte & 0x0f
+        };
+        checksummed.push(if nibble >= 8 {
+            digit.to_ascii_uppercase()
+        } else {
+            digit
+        });
+    }
+    checksummed
+}
+
+fn format<C: StealthAddressOnCurve>(kind: Kind, bytes: &[u8], format: TextFormat) -> String {
+    let prefix = kind.prefix::<C>();
+    match format {
+        TextFormat::Hex => checksummed_hex(&prefix, bytes),
+        TextFormat::Bech32m => {
+            let hrp = Hrp::parse(&prefix).expect("curve names are valid bech32m prefixes");
+            bech32::encode::<Bech32m>(hrp, bytes).expect("keys fit in a bech32m string")
+        }
+        TextFormat::Base58Check => {
+            let mut payload = keccak256(prefix.as_bytes())[..TAG_SIZE].to_vec();
+            payload.extend_from_slice(bytes);
+            bs58::encode(payload).with_check().into_string()
+        }
+    }
+}
+
+/// Parses text in any of the formats, telling them apart by their alphabets: only bech32m
+/// prefixes contain an underscore, and only hex starts with `0x`. Hex does not name its prefix,
+/// so hex for another curve or kind of key fails its checksum.
+fn parse<C: StealthAddressOnCurve>(kind: Kind, text: &str) -> Result<Vec<u8>, ParseError> {
+    let expected = kind.prefix::<C>();
+    if let Some(digits) = text.strip_prefix("0x") {
+        let bytes = hex::decode(digits).map_err(|err| ParseError::Malformed(err.to_string()))?;
+        if checksummed_hex(&expected, &bytes) != text {
+            return Err(ParseError::InvalidChecksum);
+        }
+        Ok(bytes)
+    } else if text.contains('_') {
+        let checked = CheckedHrpstring::new::<Bech32m>(text).map_err(|err| match err {
+            CheckedHrpstringError::Checksum(ChecksumError::InvalidResidue) => {
+                ParseError::InvalidChecksum
+            }
+            err => ParseError::Malformed(describe(&err)),
+        })?;
+        let found = checked.hrp().to_lowercase();
+        if found != expected {
+            return Err(ParseError::WrongPrefix {
+                expected,
+                found: Some(found),
+            });
+        }
+        // the bits left over after the last byte must be zero, so that a key has a single string
+        checked
+            .validate_segwit_padding()
+            .map_err(|err| ParseError::Malformed(describe(&err)))?;
+        Ok(checked.byte_iter().collect())
+    } else {
+        let payload = bs58::decode(text)
+            .with_check(None)
+            .into_vec()
+            .map_err(|err| match err {
+                bs58::decode::Error::InvalidChecksum { .. } => ParseError::InvalidChecksum,
+                err => ParseError::Malformed(err.to_string()),
+            })?;
+        if payload.len() < TAG_SIZE
+            || payload[..TAG_SIZE] != keccak256(expected.as_bytes())[..TAG_SIZE]
+        {
+            return Err(ParseError::WrongPrefix {
+                expected,
+                found: None,
+            });
+        }
+        Ok(payload[TAG_SIZE..].to_vec())
+    }
+}
+
+/// Describes an error along with its sources, as the bech32 errors leave the details to them.
+fn describe(err: &dyn std::error::Error) -> String {
+    let mut description = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        description.push_str(": ");
+        description.push_str(&err.to_string());
+        source = err.source();
+    }
+    description
+}
+
+fn check_length(bytes: &[u8], expected: usize) -> Result<(), ParseError> {
+    if bytes.len() != expected {
+        return Err(ParseError::InvalidLength {
+            expected,
+            found: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+/// A public key, with `Display` and `FromStr` in the text formats.
+pub struct PublicKey<C: StealthAddressOnCurve>(pub C::Projective);
+
+/// A secret key, with `Display` and `FromStr` in the text formats. Its `Debug` output is
+/// redacted.
+pub struct SecretKey<C: StealthAddressOnCurve>(pub Fr<C>);
+
+impl<C: StealthAddressOnCurve> PublicKey<C> {
+    pub fn to_text(&self, format: TextFormat) -> String {
+        self::format::<C>(Kind::PublicKey, &C::encode_point(&self.0), format)
+    }
+}
+
+impl<C: StealthAddressOnCurve> SecretKey<C> {
+    pub fn to_text(&self, format: TextFormat) -> String {
+        self::format::<C>(Kind::SecretKey, &encode(&self.0), format)
+    }
+}
+
+impl<C: StealthAddressOnCurve> MetaAddress<C> {
+    pub fn to_text(&self, format: TextFormat) -> String {
+        let mut bytes = C::encode_point(&self.spending_public_key);
+        bytes.extend(C::encode_point(&self.viewing_public_key));
+        self::format::<C>(Kind::MetaAddress, &bytes, format)
+    }
+}
+
+impl<C: StealthAddressOnCurve> FromStr for PublicKey<C> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let bytes = parse::<C>(Kind::PublicKey, text)?;
+        check_length(&bytes, C::COMPRESSED_POINT_SIZE)?;
+        C::decode_point(&bytes)
+            .map(PublicKey)
+            .map_err(|_| ParseError::InvalidEncoding)
+    }
+}
+
+impl<C: StealthAddressOnCurve> FromStr for SecretKey<C> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let bytes = parse::<C>(Kind::SecretKey, text)?;
+        check_length(&bytes, C::SCALAR_SIZE)?;
+        decode(&bytes)
+            .map(SecretKey)
+            .map_err(|_| ParseError::InvalidEncoding)
+    }
+}
+
+impl<C: StealthAddressOnCurve> FromStr for MetaAddress<C> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let bytes = parse::<C>(Kind::MetaAddress, text)?;
+        check_length(&bytes, 2 * C::COMPRESSED_POINT_SIZE)?;
+        let (spending_public_key, viewing_public_key) = bytes.split_at(C::COMPRESSED_POINT_SIZE);
+        Ok(MetaAddress::new(
+            C::decode_point(spending_public_key).map_err(|_| ParseError::InvalidEncoding)?,
+            C::decode_point(viewing_public_key).map_err(|_| ParseError::InvalidEncoding)?,
+        ))
+    }
+}
+
+impl<C: StealthAddressOnCurve> Display for PublicKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_text(TextFormat::Bech32m))
+    }
+}
+
+impl<C: StealthAddressOnCurve> Display for SecretKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_text(TextFormat::Bech32m))
+    }
+}
+
+impl<C: StealthAddressOnCurve> Display for MetaAddress<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_text(TextFormat::Bech32m))
+    }
+}
+
+// implemented by hand, as deriving would require the curve marker to implement them too
+impl<C: StealthAddressOnCurve> Clone for PublicKey<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: StealthAddressOnCurve> Copy for PublicKey<C> {}
+
+impl<C: StealthAddressOnCurve> PartialEq for PublicKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: StealthAddressOnCurve> Eq for PublicKey<C> {}
+
+impl<C: StealthAddressOnCurve> Debug for PublicKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PublicKey").field(&self.0).finish()
+    }
+}
+
+impl<C: StealthAddressOnCurve> Clone for SecretKey<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: StealthAddressOnCurve> Copy for SecretKey<C> {}
+
+impl<C: StealthAddressOnCurve> PartialEq for SecretKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: StealthAddressOnCurve> Eq for SecretKey<C> {}
+
+impl<C: StealthAddressOnCurve> Debug for SecretKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_checksum_is_eip_55_for_addresses() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let bytes = hex::decode(&address[2..]).unwrap();
+            assert_eq!(checksummed_hex("", &bytes), address);
+        }
+    }
+}
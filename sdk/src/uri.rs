@@ -0,0 +1,470 @@
+//! Payment requests as `stealth:` URIs, after BIP-21, so that a recipient can share a meta-address
+//! along with what they are asking for, e.g. as a QR code:
+//!
+//! ```text
+//! stealth:st_secp256k11...?scheme=1&chain_id=1&amount=0.5&asset=ETH&memo=Invoice%2042&expiry=1767225600&sig=...
+//! ```
+//!
+//! The path is the meta-address, in any of the `text` formats (bech32m, which names the curve, by
+//! default). The parameters are all optional:
+//!
+//! - `scheme`: the ERC-5564 scheme id of the curve, written for the curves that have one.
+//! - `chain_id`: the chain to pay on.
+//! - `amount`: the requested amount, as a decimal number in units of the asset.
+//! - `asset`: what to pay in, e.g. a ticker or a token contract address.
+//! - `memo`: a note for the payer.
+//! - `expiry`: the unix time, in seconds, after which the request should not be paid.
+//! - `sig`: a signature by the spending key over the URI before it, in hex, so that a payer can
+//!   check the request comes from the owner of the meta-address. It must be the last parameter.
+//!
+//! Values are percent-encoded. Unknown parameters are ignored, unless they start with `req-`, in
+//! which case the request cannot be understood and is rejected.
+//!
+//! ```
+//! # #[cfg(feature = "secp256k1")]
+//! # {
+//! use stealth_address_kit::prelude::*;
+//! use stealth_address_kit::uri::PaymentRequest;
+//!
+//! let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+//! let (_, viewing_public_key) = Secp256k1::random_keypair();
+//! let meta_address = MetaAddress::<Secp256k1>::new(spending_public_key, viewing_public_key);
+//!
+//! let request = PaymentRequest::new(meta_address)
+//!     .with_chain_id(1)
+//!     .with_amount("0.5")
+//!     .unwrap()
+//!     .with_asset("ETH")
+//!     .with_memo("Invoice 42");
+//! let uri = request.to_signed_uri(&spending_key);
+//!
+//! assert_eq!(PaymentRequest::<Secp256k1>::from_signed_uri(&uri), Ok(request));
+//! # }
+//! ```
+use crate::encoding::{decode, encode};
+use crate::meta_address::MetaAddress;
+use crate::prelude::Fr;
+use crate::stealth_addresses::StealthAddressOnCurve;
+use crate::text::{ParseError, TextFormat};
+use ark_ec::Group;
+use ark_ff::PrimeField;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_keccak::{Hasher, Keccak};
+
+/// The scheme of payment request URIs.
+pub const SCHEME: &str = "stealth";
+
+/// Domain separation for the challenges of request signatures.
+const CHALLENGE_DOMAIN: &[u8] = b"stealth_address_kit/uri/challenge";
+/// Domain separation for the nonces of request signatures.
+const NONCE_DOMAIN: &[u8] = b"stealth_address_kit/uri/nonce";
+
+/// Why a payment request URI could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriError {
+    /// The URI does not have the `stealth:` scheme.
+    NotAStealthUri,
+    /// The meta-address could not be parsed.
+    MetaAddress(ParseError),
+    /// A parameter has a value that is not valid for it, e.g. an amount that is not a number.
+    InvalidParameter(String),
+    /// A parameter is given more than once.
+    DuplicateParameter(String),
+    /// A `req-` parameter, which this parser does not understand, is required.
+    UnsupportedRequirement(String),
+    /// The signature is malformed, or not by the spending key of the meta-address.
+    InvalidSignature,
+    /// The URI is not signed, but a signature is required.
+    MissingSignature,
+}
+
+impl Display for UriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UriError::NotAStealthUri => write!(f, "not a `{SCHEME}:` URI"),
+            UriError::MetaAddress(err) => write!(f, "invalid meta-address: {err}"),
+            UriError::InvalidParameter(name) => write!(f, "invalid `{name}` parameter"),
+            UriError::DuplicateParameter(name) => write!(f, "duplicate `{name}` parameter"),
+            UriError::UnsupportedRequirement(name) => {
+                write!(f, "unsupported required parameter `{name}`")
+            }
+            UriError::InvalidSignature => {
+                write!(
+                    f,
+                    "the request is not signed by the owner of the meta-address"
+                )
+            }
+            UriError::MissingSignature => write!(f, "the request is not signed"),
+        }
+    }
+}
+
+impl std::error::Error for UriError {}
+
+/// A request for a payment to a meta-address, with a builder and a parser for its `stealth:` URI.
+pub struct PaymentRequest<C: StealthAddressOnCurve> {
+    pub meta_address: MetaAddress<C>,
+    pub chain_id: Option<u64>,
+    /// A decimal number, kept as text so that no precision is lost.
+    pub amount: Option<String>,
+    pub asset: Option<String>,
+    pub memo: Option<String>,
+    /// The unix time, in seconds, after which the request should not be paid.
+    pub expiry: Option<u64>,
+}
+
+impl<C: StealthAddressOnCurve> PaymentRequest<C> {
+    pub fn new(meta_address: MetaAddress<C>) -> Self {
+        PaymentRequest {
+            meta_address,
+            chain_id: None,
+            amount: None,
+            asset: None,
+            memo: None,
+            expiry: None,
+        }
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the amount, which must be a decimal number such as `1` or `0.25`.
+    pub fn with_amount(mut self, amount: &str) -> Result<Self, UriError> {
+        if !is_decimal(amount) {
+            return Err(UriError::InvalidParameter("amount".to_owned()));
+        }
+        self.amount = Some(amount.to_owned());
+        Ok(self)
+    }
+
+    pub fn with_asset(mut self, asset: &str) -> Self {
+        self.asset = Some(asset.to_owned());
+        self
+    }
+
+    pub fn with_memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_owned());
+        self
+    }
+
+    pub fn with_expiry(mut self, expiry: u64) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Whether the request has expired at the given unix time, in seconds.
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expiry.is_some_and(|expiry| now > expiry)
+    }
+
+    /// Whether the request has expired, by the system clock.
+    pub fn is_expired(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.is_expired_at(now)
+    }
+
+    /// Builds the URI, without a signature.
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!(
+            "{SCHEME}:{}",
+            self.meta_address.to_text(TextFormat::Bech32m)
+        );
+        let mut parameters = Vec::new();
+        if let Some(scheme_id) = C::SCHEME_ID {
+            parameters.push(("scheme", scheme_id.to_string()));
+        }
+        if let Some(chain_id) = self.chain_id {
+            parameters.push(("chain_id", chain_id.to_string()));
+        }
+        if let Some(amount) = &self.amount {
+            parameters.push(("amount", amount.clone()));
+        }
+        if let Some(asset) = &self.asset {
+            parameters.push(("asset", percent_encode(asset)));
+        }
+        if let Some(memo) = &self.memo {
+            parameters.push(("memo", percent_encode(memo)));
+        }
+        if let Some(expiry) = self.expiry {
+            parameters.push(("expiry", expiry.to_string()));
+        }
+        for (index, (name, value)) in parameters.iter().enumerate() {
+            uri.push(if index == 0 { '?' } else { '&' });
+            uri.push_str(name);
+            uri.push('=');
+            uri.push_str(value);
+        }
+        uri
+    }
+
+    /// Builds the URI, signed by the spending key of the meta-address.
+    pub fn to_signed_uri(&self, spending_key: &Fr<C>) -> String {
+        let uri = self.to_uri();
+        let signature = sign::<C>(spending_key, uri.as_bytes());
+        let separator = if uri.contains('?') { '&' } else { '?' };
+        format!("{uri}{separator}sig={}", hex::encode(signature))
+    }
+
+    /// Parses a URI, checking its signature if it has one.
+    pub fn from_uri(uri: &str) -> Result<Self, UriError> {
+        Self::parse(uri).map(|(request, _)| request)
+    }
+
+    /// Parses a URI, requiring it to be signed by the spending key of the meta-address.
+    pub fn from_signed_uri(uri: &str) -> Result<Self, UriError> {
+        match Self::parse(uri)? {
+            (request, true) => Ok(request),
+            (_, false) => Err(UriError::MissingSignature),
+        }
+    }
+
+    /// Parses a URI, returning whether it carried a valid signature.
+    fn parse(uri: &str) -> Result<(Self, bool), UriError> {
+        let (scheme, rest) = uri.split_once(':').ok_or(UriError::NotAStealthUri)?;
+        if !scheme.eq_ignore_ascii_case(SCHEME) {
+            return Err(UriError::NotAStealthUri);
+        }
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let meta_address = path.parse().map_err(UriError::MetaAddress)?;
+        let mut request = PaymentRequest::new(meta_address);
+
+        let mut seen = Vec::new();
+        let mut signed = false;
+        let mut offset = uri.len() - query.len();
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            if seen.contains(&name) {
+                return Err(UriError::DuplicateParameter(name.to_owned()));
+            }
+            seen.push(name);
+            let invalid = || UriError::InvalidParameter(name.to_owned());
+
+            match name {
+                "scheme" => {
+                    let scheme_id = value.parse::<u64>().map_err(|_| invalid())?;
+                    if C::SCHEME_ID != Some(scheme_id) {
+                        return Err(invalid());
+                    }
+                }
+                "chain_id" => request.chain_id = Some(value.parse().map_err(|_| invalid())?),
+                "amount" => {
+                    if !is_decimal(value) {
+                        return Err(invalid());
+                    }
+                    request.amount = Some(value.to_owned());
+                }
+                "asset" => request.asset = Some(percent_decode(value).ok_or_else(invalid)?),
+                "memo" => request.memo = Some(percent_decode(value).ok_or_else(invalid)?),
+                "expiry" => request.expiry = Some(value.parse().map_err(|_| invalid())?),
+                "sig" => {
+                    // the signature covers everything before it, so it must come last
+                    if offset + parameter.len() != uri.len() {
+                        return Err(UriError::InvalidSignature);
+                    }
+                    let signature = hex::decode(value).map_err(|_| UriError::InvalidSignature)?;
+                    let message = &uri[..offset - 1];
+                    if !verify::<C>(
+                        &request.meta_address.spending_public_key,
+                        message.as_bytes(),
+                        &signature,
+                    ) {
+                        return Err(UriError::InvalidSignature);
+                    }
+                    signed = true;
+                }
+                name if name.starts_with("req-") => {
+                    return Err(UriError::UnsupportedRequirement(name.to_owned()));
+                }
+                _ => {}
+            }
+            offset += parameter.len() + 1;
+        }
+        Ok((request, signed))
+    }
+}
+
+impl<C: StealthAddressOnCurve> FromStr for PaymentRequest<C> {
+    type Err = UriError;
+
+    fn from_str(uri: &str) -> Result<Self, UriError> {
+        Self::from_uri(uri)
+    }
+}
+
+impl<C: StealthAddressOnCurve> Display for PaymentRequest<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_uri())
+    }
+}
+
+// implemented by hand, as deriving would require the curve marker to implement them too
+impl<C: StealthAddressOnCurve> Clone for PaymentRequest<C> {
+    fn clone(&self) -> Self {
+        PaymentRequest {
+            meta_address: self.meta_address,
+            chain_id: self.chain_id,
+            amount: self.amount.clone(),
+            asset: self.asset.clone(),
+            memo: self.memo.clone(),
+            expiry: self.expiry,
+        }
+    }
+}
+
+impl<C: StealthAddressOnCurve> PartialEq for PaymentRequest<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.meta_address == other.meta_address
+            && self.chain_id == other.chain_id
+            && self.amount == other.amount
+            && self.asset == other.asset
+            && self.memo == other.memo
+            && self.expiry == other.expiry
+    }
+}
+
+impl<C: StealthAddressOnCurve> Eq for PaymentRequest<C> {}
+
+impl<C: StealthAddressOnCurve> Debug for PaymentRequest<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PaymentRequest")
+            .field("meta_address", &self.meta_address)
+            .field("chain_id", &self.chain_id)
+            .field("amount", &self.amount)
+            .field("asset", &self.asset)
+            .field("memo", &self.memo)
+            .field("expiry", &self.expiry)
+            .finish()
+    }
+}
+
+fn is_decimal(amount: &str) -> bool {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, "0"));
+    [whole, fraction]
+        .iter()
+        .all(|digits| !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit()))
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let digits = [bytes.next()?, bytes.next()?];
+            decoded.push(u8::from_str_radix(std::str::from_utf8(&digits).ok()?, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Hashes the input into a scalar, without bias: the Keccak-256 hashes of the input after a
+/// counter are concatenated until they are 128 bits longer than the order of the curve, and
+/// reduced by it.
+fn wide_hash_to_fr<C: StealthAddressOnCurve>(domain: &[u8], input: &[&[u8]]) -> Fr<C> {
+    let size = (Fr::<C>::MODULUS_BIT_SIZE as usize + 128).div_ceil(8);
+    let mut bytes = Vec::with_capacity(size.next_multiple_of(32));
+    for counter in 0u8.. {
+        if bytes.len() >= size {
+            break;
+        }
+        let mut hasher = Keccak::v256();
+        hasher.update(&[counter]);
+        hasher.update(domain);
+        input.iter().for_each(|part| hasher.update(part));
+        let mut hash = [0; 32];
+        hasher.finalize(&mut hash);
+        bytes.extend_from_slice(&hash);
+    }
+    Fr::<C>::from_le_bytes_mod_order(&bytes[..size])
+}
+
+fn challenge<C: StealthAddressOnCurve>(
+    nonce_point: &C::Projective,
+    public_key: &C::Projective,
+    message: &[u8],
+) -> Fr<C> {
+    wide_hash_to_fr::<C>(
+        CHALLENGE_DOMAIN,
+        &[
+            &C::encode_point(nonce_point),
+            &C::encode_point(public_key),
+            message,
+        ],
+    )
+}
+
+/// Derives the nonce of a signature from the key and the message, reduced from a hash wider than
+/// the order so that it is uniform over the whole scalar field, on every curve.
+fn nonce<C: StealthAddressOnCurve>(private_key: &Fr<C>, message: &[u8]) -> Fr<C> {
+    wide_hash_to_fr::<C>(NONCE_DOMAIN, &[&encode(private_key), message])
+}
+
+/// Signs a message with a Schnorr signature, whose nonce is derived from the key and the message,
+/// as the encoded nonce point followed by the encoded scalar.
+fn sign<C: StealthAddressOnCurve>(private_key: &Fr<C>, message: &[u8]) -> Vec<u8> {
+    let nonce = nonce::<C>(private_key, message);
+    let nonce_point = C::derive_public_key(&nonce);
+    let challenge = challenge::<C>(&nonce_point, &C::derive_public_key(private_key), message);
+    let mut signature = C::encode_point(&nonce_point);
+    signature.extend(encode(&(nonce + challenge * private_key)));
+    signature
+}
+
+fn verify<C: StealthAddressOnCurve>(
+    public_key: &C::Projective,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    if signature.len() != C::COMPRESSED_POINT_SIZE + C::SCALAR_SIZE {
+        return false;
+    }
+    let (nonce_point, scalar) = signature.split_at(C::COMPRESSED_POINT_SIZE);
+    let (Ok(nonce_point), Ok(scalar)) = (C::decode_point(nonce_point), decode::<Fr<C>>(scalar))
+    else {
+        return false;
+    };
+    let challenge = challenge::<C>(&nonce_point, public_key, message);
+    C::Projective::generator() * scalar == nonce_point + *public_key * challenge
+}
+
+#[cfg(all(test, feature = "bw6_761"))]
+mod tests {
+    use super::*;
+    use crate::BW6_761;
+    use ark_ff::BigInteger;
+
+    #[test]
+    fn nonces_cover_the_whole_scalar_field() {
+        // the order of BW6_761 has 377 bits, far more than a single 256-bit hash
+        let private_key = Fr::<BW6_761>::from(7u8);
+        let bits = (0..64u8)
+            .map(|message| {
+                nonce::<BW6_761>(&private_key, &[message])
+                    .into_bigint()
+                    .num_bits()
+            })
+            .max()
+            .unwrap();
+        assert_eq!(bits, Fr::<BW6_761>::MODULUS_BIT_SIZE);
+    }
+}
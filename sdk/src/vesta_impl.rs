@@ -6,10 +6,19 @@ pub struct Vesta;
 
 impl StealthAddressOnCurve for Vesta {
     type Projective = Projective;
+    const NAME: &'static str = "vesta";
 }
 
 #[cfg(feature = "ffi")]
 use crate::define_curve_ffi;
 #[cfg(feature = "ffi")]
 define_curve_ffi!(vesta, Vesta, Fr, Projective, 32, 33);
+#[cfg(feature = "wasm")]
+use crate::define_curve_wasm;
+#[cfg(feature = "wasm")]
+define_curve_wasm!(Vesta, Fr, Projective);
+#[cfg(feature = "python")]
+use crate::define_curve_python;
+#[cfg(feature = "python")]
+define_curve_python!(Vesta, Fr, Projective);
 define_curve_tests!(Vesta);
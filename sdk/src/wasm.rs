@@ -0,0 +1,157 @@
+#[macro_export]
+macro_rules! define_curve_wasm {
+    ($Curve:ident, $Fr:ty, $Projective:ty) => {
+        mod wasm {
+            use super::*;
+            use paste::paste;
+            use wasm_bindgen::prelude::*;
+            use $crate::wasm_prelude::{from_bytes_or_hex, point_from_bytes_or_hex, to_bytes, BytesOrHex, KeyPair, StealthAddress};
+
+            paste! {
+                /// Exposes the StealthAddressOnCurve operations to javascript as static methods.
+                #[wasm_bindgen(js_name = $Curve)]
+                pub struct [<Wasm $Curve>];
+
+                #[wasm_bindgen(js_class = $Curve)]
+                impl [<Wasm $Curve>] {
+                    #[wasm_bindgen(js_name = generateRandomFr)]
+                    pub fn generate_random_fr() -> Vec<u8> {
+                        to_bytes(&<$Curve>::generate_random_fr())
+                    }
+
+                    #[wasm_bindgen(js_name = derivePublicKey)]
+                    pub fn derive_public_key(private_key: &BytesOrHex) -> Result<Vec<u8>, JsError> {
+                        let private_key: $Fr = from_bytes_or_hex(private_key)?;
+                        Ok(<$Curve>::encode_point(&<$Curve>::derive_public_key(&private_key)))
+                    }
+
+                    #[wasm_bindgen(js_name = randomKeypair)]
+                    pub fn random_keypair() -> KeyPair {
+                        let (private_key, public_key) = <$Curve>::random_keypair();
+                        KeyPair::new(to_bytes(&private_key), <$Curve>::encode_point(&public_key))
+                    }
+
+                    #[wasm_bindgen(js_name = generateStealthAddress)]
+                    pub fn generate_stealth_address(
+                        viewing_public_key: &BytesOrHex,
+                        spending_public_key: &BytesOrHex,
+                        ephemeral_private_key: &BytesOrHex,
+                    ) -> Result<StealthAddress, JsError> {
+                        let viewing_public_key = point_from_bytes_or_hex::<$Curve>(viewing_public_key)?;
+                        let spending_public_key = point_from_bytes_or_hex::<$Curve>(spending_public_key)?;
+                        let ephemeral_private_key: $Fr = from_bytes_or_hex(ephemeral_private_key)?;
+
+                        let (stealth_address, view_tag) = <$Curve>::generate_stealth_address(
+                            viewing_public_key,
+                            spending_public_key,
+                            ephemeral_private_key,
+                        );
+                        Ok(StealthAddress::new(<$Curve>::encode_point(&stealth_address), view_tag))
+                    }
+
+                    /// Returns `undefined` when the view tag does not match.
+                    #[wasm_bindgen(js_name = generateStealthPrivateKey)]
+                    pub fn generate_stealth_private_key(
+                        ephemeral_public_key: &BytesOrHex,
+                        viewing_key: &BytesOrHex,
+                        spending_key: &BytesOrHex,
+                        view_tag: u64,
+                    ) -> Result<Option<Vec<u8>>, JsError> {
+                        let ephemeral_public_key = point_from_bytes_or_hex::<$Curve>(ephemeral_public_key)?;
+                        let viewing_key: $Fr = from_bytes_or_hex(viewing_key)?;
+                        let spending_key: $Fr = from_bytes_or_hex(spending_key)?;
+
+                        Ok(<$Curve>::generate_stealth_private_key(
+                            ephemeral_public_key,
+                            viewing_key,
+                            spending_key,
+                            view_tag,
+                        )
+                        .map(|stealth_private_key| to_bytes(&stealth_private_key)))
+                    }
+                }
+
+                #[cfg(all(test, target_arch = "wasm32"))]
+                mod wasm_tests {
+                    use super::*;
+                    use js_sys::Uint8Array;
+                    use wasm_bindgen::JsCast;
+                    use wasm_bindgen_test::wasm_bindgen_test;
+
+                    fn bytes(value: &[u8]) -> BytesOrHex {
+                        JsValue::from(Uint8Array::from(value)).unchecked_into()
+                    }
+
+                    fn hex_str(value: &[u8]) -> BytesOrHex {
+                        JsValue::from_str(&format!("0x{}", hex::encode(value))).unchecked_into()
+                    }
+
+                    #[wasm_bindgen_test]
+                    fn random_keypair_happy_path() {
+                        let keypair = [<Wasm $Curve>]::random_keypair();
+                        let derived = [<Wasm $Curve>]::derive_public_key(&bytes(&keypair.private_key())).unwrap();
+                        assert_eq!(derived, keypair.public_key());
+                    }
+
+                    #[wasm_bindgen_test]
+                    fn generate_stealth_address_matches_rust() {
+                        let (viewing_key, viewing_public_key) = <$Curve>::random_keypair();
+                        let (spending_key, spending_public_key) = <$Curve>::random_keypair();
+                        let (ephemeral_private_key, ephemeral_public_key) = <$Curve>::random_keypair();
+
+                        let (expected_address, expected_view_tag) = <$Curve>::generate_stealth_address(
+                            viewing_public_key,
+                            spending_public_key,
+                            ephemeral_private_key,
+                        );
+
+                        // mix both input encodings
+                        let stealth_address = [<Wasm $Curve>]::generate_stealth_address(
+                            &bytes(&<$Curve>::encode_point(&viewing_public_key)),
+                            &hex_str(&<$Curve>::encode_point(&spending_public_key)),
+                            &bytes(&to_bytes(&ephemeral_private_key)),
+                        )
+                        .unwrap();
+                        assert_eq!(stealth_address.stealth_address(), <$Curve>::encode_point(&expected_address));
+                        assert_eq!(stealth_address.view_tag(), expected_view_tag);
+
+                        let stealth_private_key = [<Wasm $Curve>]::generate_stealth_private_key(
+                            &hex_str(&<$Curve>::encode_point(&ephemeral_public_key)),
+                            &bytes(&to_bytes(&viewing_key)),
+                            &hex_str(&to_bytes(&spending_key)),
+                            stealth_address.view_tag(),
+                        )
+                        .unwrap()
+                        .unwrap();
+                        let derived = [<Wasm $Curve>]::derive_public_key(&bytes(&stealth_private_key)).unwrap();
+                        assert_eq!(derived, stealth_address.stealth_address());
+
+                        let mismatched = [<Wasm $Curve>]::generate_stealth_private_key(
+                            &bytes(&<$Curve>::encode_point(&ephemeral_public_key)),
+                            &bytes(&to_bytes(&viewing_key)),
+                            &bytes(&to_bytes(&spending_key)),
+                            stealth_address.view_tag().wrapping_add(1),
+                        )
+                        .unwrap();
+                        assert!(mismatched.is_none());
+                    }
+
+                    #[wasm_bindgen_test]
+                    fn rejects_malformed_input() {
+                        let (private_key, _) = <$Curve>::random_keypair();
+                        let mut encoded = to_bytes(&private_key);
+                        encoded.push(0);
+
+                        assert!([<Wasm $Curve>]::derive_public_key(&bytes(&encoded)).is_err());
+                        assert!([<Wasm $Curve>]::derive_public_key(&hex_str(&encoded)).is_err());
+                        assert!([<Wasm $Curve>]::derive_public_key(
+                            &JsValue::from_str("not hex").unchecked_into()
+                        )
+                        .is_err());
+                        assert!([<Wasm $Curve>]::derive_public_key(&JsValue::from(1).unchecked_into()).is_err());
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,112 @@
+use crate::encoding::decode;
+pub(crate) use crate::encoding::encode as to_bytes;
+use crate::StealthAddressOnCurve;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A key or point, given either as raw encoded bytes or as a hex string
+    /// (with or without a `0x` prefix).
+    #[wasm_bindgen(typescript_type = "Uint8Array | string")]
+    pub type BytesOrHex;
+}
+
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct KeyPair {
+    private_key: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl KeyPair {
+    #[wasm_bindgen(getter, js_name = privateKey)]
+    pub fn private_key(&self) -> Vec<u8> {
+        self.private_key.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = privateKeyHex)]
+    pub fn private_key_hex(&self) -> String {
+        hex::encode(&self.private_key)
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKeyHex)]
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(&self.public_key)
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct StealthAddress {
+    stealth_address: Vec<u8>,
+    view_tag: u64,
+}
+
+#[wasm_bindgen]
+impl StealthAddress {
+    #[wasm_bindgen(getter, js_name = stealthAddress)]
+    pub fn stealth_address(&self) -> Vec<u8> {
+        self.stealth_address.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = stealthAddressHex)]
+    pub fn stealth_address_hex(&self) -> String {
+        hex::encode(&self.stealth_address)
+    }
+
+    #[wasm_bindgen(getter, js_name = viewTag)]
+    pub fn view_tag(&self) -> u64 {
+        self.view_tag
+    }
+}
+
+impl KeyPair {
+    pub(crate) fn new(private_key: Vec<u8>, public_key: Vec<u8>) -> Self {
+        KeyPair {
+            private_key,
+            public_key,
+        }
+    }
+}
+
+impl StealthAddress {
+    pub(crate) fn new(stealth_address: Vec<u8>, view_tag: u64) -> Self {
+        StealthAddress {
+            stealth_address,
+            view_tag,
+        }
+    }
+}
+
+fn bytes_or_hex(value: &BytesOrHex) -> Result<Vec<u8>, JsError> {
+    if let Some(hex_str) = value.as_string() {
+        Ok(hex::decode(hex_str.strip_prefix("0x").unwrap_or(&hex_str))?)
+    } else if let Some(array) = value.dyn_ref::<Uint8Array>() {
+        Ok(array.to_vec())
+    } else {
+        Err(JsError::new("expected a Uint8Array or a hex string"))
+    }
+}
+
+/// Decodes a compressed key, only accepting its canonical encoding.
+pub(crate) fn from_bytes_or_hex<T: CanonicalDeserialize + CanonicalSerialize>(
+    value: &BytesOrHex,
+) -> Result<T, JsError> {
+    Ok(decode(&bytes_or_hex(value)?)?)
+}
+
+/// Decodes a point in the standard compressed encoding of the curve.
+pub(crate) fn point_from_bytes_or_hex<C: StealthAddressOnCurve>(
+    value: &BytesOrHex,
+) -> Result<C::Projective, JsError> {
+    Ok(C::decode_point(&bytes_or_hex(value)?)?)
+}
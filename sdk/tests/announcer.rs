@@ -0,0 +1,222 @@
+//! Decodes the `eth_getLogs` response in `fixtures/announcement_logs.json` and scans it for the
+//! payments to the fixture keys, which are the private keys of the first two accounts of the
+//! Hardhat and Anvil test mnemonic.
+//!
+//! `fixtures/announcement_logs_generator` derives the logs with k256 and sha3, as ERC-5564 scheme
+//! 1 specifies, in the shape a node returns them, extra fields included. Nothing was announced to
+//! these keys on chain, so the block numbers and hashes are made up. The response holds five
+//! announcements: an Ether payment to the fixture keys, one to another viewing key, an ERC-20
+//! payment to the fixture keys with a memo after the transfer, one for scheme id 2, and one that
+//! was removed in a reorganization.
+#![cfg(feature = "ethereum")]
+
+use ark_ff::PrimeField;
+use stealth_address_kit::ethereum::address_of;
+use stealth_address_kit::ethereum::announcer::{
+    announce_calldata, announcement_topic, scan_logs, Log, LogError, LoggedAnnouncement,
+    ANNOUNCER_ADDRESS,
+};
+use stealth_address_kit::prelude::*;
+
+/// The private key of an account of the test mnemonic.
+fn account(private_key: &str) -> Fr<Secp256k1> {
+    Fr::<Secp256k1>::from_be_bytes_mod_order(&hex::decode(private_key).unwrap())
+}
+
+/// The viewing key is account 1's, and the spending key account 0's.
+fn keys() -> (Fr<Secp256k1>, Fr<Secp256k1>) {
+    (
+        account("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"),
+        account("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
+    )
+}
+
+fn logs() -> Vec<Log> {
+    let response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/announcement_logs.json")).unwrap();
+    serde_json::from_value(response["result"].clone()).unwrap()
+}
+
+fn amount(value: u64) -> [u8; 32] {
+    let mut amount = [0; 32];
+    amount[24..].copy_from_slice(&value.to_be_bytes());
+    amount
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Encodes two byte strings as the data of a log, each padded to whole 32-byte words.
+fn event_data(first: &[u8], second: &[u8]) -> String {
+    let word = |value: usize| format!("{value:064x}");
+    let tail = |bytes: &[u8]| {
+        let padding = "00".repeat(bytes.len().next_multiple_of(32) - bytes.len());
+        format!("{}{}{padding}", word(bytes.len()), hex::encode(bytes))
+    };
+    let first_tail = tail(first);
+    format!(
+        "0x{}{}{first_tail}{}",
+        word(64),
+        word(64 + first_tail.len() / 2),
+        tail(second)
+    )
+}
+
+#[test]
+fn keys_are_the_test_accounts() {
+    let (viewing_key, spending_key) = keys();
+    assert_eq!(
+        hex::encode(address_of(&Secp256k1::derive_public_key(&spending_key))),
+        "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+    );
+    assert_eq!(
+        hex::encode(address_of(&Secp256k1::derive_public_key(&viewing_key))),
+        "70997970c51812dc3a010c7d01b50e0d17dc79c8"
+    );
+}
+
+#[test]
+fn decodes_announcement_logs() {
+    let logs = logs();
+    assert_eq!(
+        hex::encode(announcement_topic()),
+        "5f0eab8057630ba7676c49b4f21a0231414e79474595be8e4c432fbf6bf0f4e7"
+    );
+
+    let announcement = LoggedAnnouncement::from_log(&logs[0]).unwrap();
+    assert_eq!(announcement.announcer, ANNOUNCER_ADDRESS);
+    assert_eq!(
+        hex::encode(announcement.stealth_address),
+        "915c937b02b12fc3b8d03fac3e9039a7a5e05b63"
+    );
+    assert_eq!(
+        hex::encode(announcement.caller),
+        "976ea74026e726554db657fa54763abd0c3a0aa9"
+    );
+    assert_eq!(
+        announcement.metadata.transfer,
+        Some(Transfer::Ether {
+            amount: amount(100_000_000_000_000_000),
+        })
+    );
+    assert_eq!(announcement.block_number, Some(20_000_000));
+    assert_eq!(announcement.log_index, Some(3));
+    assert_eq!(
+        announcement.transaction_hash.map(hex::encode).as_deref(),
+        Some("5be045801b511f3cdb44539e619c704052e5f41edffb34f32c42768ba3c4dd70")
+    );
+
+    assert_eq!(
+        LoggedAnnouncement::from_log(&logs[3]),
+        Err(LogError::UnsupportedScheme)
+    );
+    assert_eq!(
+        LoggedAnnouncement::from_log(&logs[4]),
+        Err(LogError::Removed)
+    );
+}
+
+#[test]
+fn scans_logs_for_the_payments_to_the_keys() {
+    let (viewing_key, spending_key) = keys();
+    let found = scan_logs(&logs(), viewing_key, spending_key);
+
+    let payments = found
+        .iter()
+        .map(|(announcement, stealth_private_key)| {
+            assert_eq!(
+                address_of(&Secp256k1::derive_public_key(stealth_private_key)),
+                announcement.stealth_address
+            );
+            (announcement.block_number, announcement.metadata.transfer)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        payments,
+        [
+            (
+                Some(20_000_000),
+                Some(Transfer::Ether {
+                    amount: amount(100_000_000_000_000_000),
+                })
+            ),
+            (
+                Some(20_000_002),
+                Some(Transfer::Erc20 {
+                    token: hex::decode("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    amount: amount(250_000_000),
+                })
+            ),
+        ]
+    );
+    assert_eq!(found[1].0.metadata.extra, b"invoice 42");
+}
+
+#[test]
+fn rejects_malformed_logs() {
+    let log = logs().remove(0);
+
+    let mut other_event = log.clone();
+    other_event.topics[0] = format!("0x{}", "00".repeat(32));
+    assert_eq!(
+        LoggedAnnouncement::from_log(&other_event),
+        Err(LogError::NotAnAnnouncement)
+    );
+    let mut missing_topic = log.clone();
+    missing_topic.topics.pop();
+    assert_eq!(
+        LoggedAnnouncement::from_log(&missing_topic),
+        Err(LogError::NotAnAnnouncement)
+    );
+    let mut truncated = log.clone();
+    truncated.data.truncate(2 + 64 * 3);
+    assert_eq!(
+        LoggedAnnouncement::from_log(&truncated),
+        Err(LogError::Malformed("ephemeralPubKey"))
+    );
+    let mut pending = log;
+    pending.block_number = None;
+    pending.log_index = Some("3".to_owned());
+    assert_eq!(
+        LoggedAnnouncement::from_log(&pending),
+        Err(LogError::Malformed("logIndex"))
+    );
+}
+
+#[test]
+fn announced_payments_are_found() {
+    let (viewing_key, spending_key) = keys();
+    let (ephemeral_private_key, ephemeral_public_key) = Secp256k1::random_keypair();
+    let (stealth_public_key, metadata) = Secp256k1::generate_stealth_address_with_metadata(
+        Secp256k1::derive_public_key(&viewing_key),
+        Secp256k1::derive_public_key(&spending_key),
+        ephemeral_private_key,
+        None,
+    );
+    let stealth_address = address_of(&stealth_public_key);
+    let calldata = announce_calldata(&stealth_address, &ephemeral_public_key, &metadata);
+
+    // the announcer logs the byte arguments of the call, after the scheme id and stealth address
+    let ephemeral_public_key_bytes = Secp256k1::encode_point(&ephemeral_public_key);
+    let metadata_bytes = metadata.encode();
+    assert!(contains(&calldata, &ephemeral_public_key_bytes));
+    assert!(contains(&calldata, &metadata_bytes));
+    let mut log = logs().remove(0);
+    log.topics[2] = format!("0x{}{}", "00".repeat(12), hex::encode(stealth_address));
+    log.data = event_data(&ephemeral_public_key_bytes, &metadata_bytes);
+    let announcement = LoggedAnnouncement::from_log(&log).unwrap();
+    assert_eq!(announcement.ephemeral_public_key, ephemeral_public_key);
+    assert_eq!(announcement.metadata, metadata);
+    assert_eq!(
+        announcement
+            .stealth_private_key(viewing_key, spending_key)
+            .map(|key| Secp256k1::derive_public_key(&key)),
+        Some(stealth_public_key)
+    );
+}
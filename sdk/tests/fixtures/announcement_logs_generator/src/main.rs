@@ -0,0 +1,194 @@
+//! Writes an `eth_getLogs` response of the ERC-5564 announcer, with the stealth addresses and view
+//! tags of scheme 1 derived with k256 and sha3: the shared secret is the compressed SEC1 encoding
+//! of the ephemeral private key times the viewing public key, its Keccak-256 hash is read as a
+//! big-endian scalar, the view tag is the first byte of the hash, and the stealth address is the
+//! address of the spending public key plus the hash times the generator.
+//!
+//! The keys are the private keys of the accounts of the Hardhat and Anvil test mnemonic, `test test
+//! test test test test test test test test test junk`. The block numbers, block hashes and
+//! transaction hashes are made up, as nothing was announced to these keys on chain.
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, ProjectivePoint, Scalar, U256};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+
+const ANNOUNCER: &str = "0x55649e01b5df198d18d95b5cc5051630cfd45564";
+const ANNOUNCEMENT_EVENT: &str = "Announcement(uint256,address,address,bytes,bytes)";
+/// USDC on Ethereum mainnet.
+const USDC: &str = "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+const TRANSFER_SELECTOR: &str = "a9059cbb";
+
+/// The private keys of accounts 0 to 6.
+const ACCOUNTS: [&str; 7] = [
+    "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    "7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+    "47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a",
+    "8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba",
+    "92db14e403b83dfe3df233f83dfa3a0d7096f21ca9b0d6d6b8d88b2b4ec1564e",
+];
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+fn account(index: usize) -> Scalar {
+    let bytes: [u8; 32] = hex::decode(ACCOUNTS[index]).unwrap().try_into().unwrap();
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes))).unwrap()
+}
+
+fn public_key(private_key: &Scalar) -> ProjectivePoint {
+    ProjectivePoint::GENERATOR * private_key
+}
+
+fn compressed(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+fn address(point: &ProjectivePoint) -> [u8; 20] {
+    let uncompressed = point.to_affine().to_encoded_point(false);
+    keccak256(&uncompressed.as_bytes()[1..])[12..]
+        .try_into()
+        .unwrap()
+}
+
+/// Returns the stealth address and the view tag of a payment.
+fn stealth_address(
+    spending_public_key: &ProjectivePoint,
+    viewing_public_key: &ProjectivePoint,
+    ephemeral_private_key: &Scalar,
+) -> ([u8; 20], u8) {
+    let shared_secret = compressed(&(*viewing_public_key * ephemeral_private_key));
+    let hashed_shared_secret = keccak256(&shared_secret);
+    let scalar = <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(hashed_shared_secret));
+    let stealth_public_key = *spending_public_key + public_key(&scalar);
+    (address(&stealth_public_key), hashed_shared_secret[0])
+}
+
+fn word(value: u64) -> String {
+    format!("{value:064x}")
+}
+
+fn topic_of_address(address: &[u8; 20]) -> String {
+    format!("0x{}{}", "00".repeat(12), hex::encode(address))
+}
+
+/// ABI encodes the two byte arguments of the event that are not indexed.
+fn event_data(ephemeral_public_key: &[u8], metadata: &[u8]) -> String {
+    let tail = |bytes: &[u8]| {
+        let padding = "00".repeat(bytes.len().next_multiple_of(32) - bytes.len());
+        format!(
+            "{}{}{padding}",
+            word(bytes.len() as u64),
+            hex::encode(bytes)
+        )
+    };
+    let first = tail(ephemeral_public_key);
+    format!(
+        "0x{}{}{first}{}",
+        word(64),
+        word(64 + first.len() as u64 / 2),
+        tail(metadata)
+    )
+}
+
+fn amount(value: u64) -> Vec<u8> {
+    let mut amount = vec![0; 24];
+    amount.extend_from_slice(&value.to_be_bytes());
+    amount
+}
+
+struct Announcement {
+    scheme_id: u64,
+    stealth_address: [u8; 20],
+    ephemeral_public_key: Vec<u8>,
+    metadata: Vec<u8>,
+    block_number: u64,
+    log_index: u64,
+    removed: bool,
+}
+
+impl Announcement {
+    fn log(&self, caller: &[u8; 20]) -> Value {
+        let data = event_data(&self.ephemeral_public_key, &self.metadata);
+        json!({
+            "address": ANNOUNCER,
+            "topics": [
+                format!("0x{}", hex::encode(keccak256(ANNOUNCEMENT_EVENT.as_bytes()))),
+                format!("0x{}", word(self.scheme_id)),
+                topic_of_address(&self.stealth_address),
+                topic_of_address(caller),
+            ],
+            "blockNumber": format!("{:#x}", self.block_number),
+            "blockHash": format!("0x{}", hex::encode(keccak256(&self.block_number.to_be_bytes()))),
+            "transactionHash": format!("0x{}", hex::encode(keccak256(data.as_bytes()))),
+            "transactionIndex": "0x1",
+            "logIndex": format!("{:#x}", self.log_index),
+            "data": data,
+            "removed": self.removed,
+        })
+    }
+}
+
+/// Pays to a stealth address of the keys, with metadata that starts with the view tag.
+fn payment(
+    spending_key: usize,
+    viewing_key: usize,
+    ephemeral_key: usize,
+    metadata: &[u8],
+    block_number: u64,
+    log_index: u64,
+) -> Announcement {
+    let ephemeral_private_key = account(ephemeral_key);
+    let (stealth_address, view_tag) = stealth_address(
+        &public_key(&account(spending_key)),
+        &public_key(&account(viewing_key)),
+        &ephemeral_private_key,
+    );
+    Announcement {
+        scheme_id: 1,
+        stealth_address,
+        ephemeral_public_key: compressed(&public_key(&ephemeral_private_key)),
+        metadata: [&[view_tag], metadata].concat(),
+        block_number,
+        log_index,
+        removed: false,
+    }
+}
+
+fn main() {
+    // account 0 holds the spending key and account 1 the viewing key, and account 2 is another
+    // recipient's viewing key
+    let ether = [&[0xee; 24][..], &amount(100_000_000_000_000_000)].concat();
+    let usdc = [
+        hex::decode(TRANSFER_SELECTOR).unwrap(),
+        hex::decode(USDC).unwrap(),
+        amount(250_000_000),
+        b"invoice 42".to_vec(),
+    ]
+    .concat();
+
+    let mut other_scheme = payment(0, 1, 5, &ether, 20_000_003, 0);
+    other_scheme.scheme_id = 2;
+    let mut removed = payment(0, 1, 6, &ether, 20_000_004, 1);
+    removed.removed = true;
+    let announcements = [
+        payment(0, 1, 3, &ether, 20_000_000, 3),
+        payment(0, 2, 4, &ether, 20_000_001, 7),
+        payment(0, 1, 4, &usdc, 20_000_002, 2),
+        other_scheme,
+        removed,
+    ];
+
+    // the sender is account 6
+    let caller = address(&public_key(&account(6)));
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": announcements.iter().map(|announcement| announcement.log(&caller)).collect::<Vec<_>>(),
+    });
+    println!("{}", serde_json::to_string_pretty(&response).unwrap());
+}
@@ -0,0 +1,114 @@
+//! Writes the Starknet fixture with starknet-rs: the public keys of some private keys, the stealth
+//! addresses of a recipient with Poseidon shared secrets and the addresses of their accounts, and
+//! the addresses of OpenZeppelin accounts deployed by `DEPLOY_ACCOUNT` transactions and by the
+//! Universal Deployer Contract.
+use serde_json::{json, Value};
+use starknet_core::utils::get_contract_address;
+use starknet_crypto::{get_public_key, poseidon_hash, FieldElement};
+use starknet_curve::curve_params::{EC_ORDER, GENERATOR};
+use starknet_curve::AffinePoint;
+
+/// The class hash of OpenZeppelin's account 0.8.1.
+const CLASS_HASH: &str = "0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f";
+/// The address of the Universal Deployer Contract.
+const UNIVERSAL_DEPLOYER: &str =
+    "0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf";
+
+fn hex(felt: &FieldElement) -> String {
+    let digits: String = felt
+        .to_bytes_be()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("0x{digits}")
+}
+
+fn felt(text: &str) -> FieldElement {
+    FieldElement::from_hex_be(text).unwrap()
+}
+
+fn mul(point: &AffinePoint, scalar: &FieldElement) -> AffinePoint {
+    point * &scalar.to_bits_le()[..]
+}
+
+/// Reduces a felt by the order of the curve, which is below the modulus of the felts.
+fn reduce(felt: FieldElement) -> FieldElement {
+    if felt.to_bytes_be() >= EC_ORDER.to_bytes_be() {
+        felt - EC_ORDER
+    } else {
+        felt
+    }
+}
+
+fn point(point: &AffinePoint) -> Value {
+    json!({ "x": hex(&point.x), "y": hex(&point.y) })
+}
+
+/// The address of the account of a public key, with the public key as salt and calldata.
+fn account_address(public_key: FieldElement, deployer: FieldElement) -> FieldElement {
+    get_contract_address(public_key, felt(CLASS_HASH), &[public_key], deployer)
+}
+
+fn main() {
+    let viewing_key = felt("0x23b2ba610516675e3eb5b98f18a8ae40286112d7b6700fd20f8ebb4a35df04b");
+    let spending_key = felt("0x28e539a23dfcecef46805edfb9aa979a362844a0f4f6ef9588fff60c97cc525");
+    let ephemeral_keys = [
+        felt("0x469fbb39eaa188d670d720432a4fc1510c3b2f9b9727277e84ff203fac39fae"),
+        felt("0x772627391b615a54eadaec554b80d39745435f7da05e2c91bfcc2e09e659db"),
+    ];
+
+    let public_keys: Vec<Value> = [
+        FieldElement::ONE,
+        viewing_key,
+        spending_key,
+        ephemeral_keys[0],
+        ephemeral_keys[1],
+    ]
+    .iter()
+    .map(|key| {
+        let public_key = mul(&GENERATOR, key);
+        assert_eq!(public_key.x, get_public_key(key));
+        json!({ "private_key": hex(key), "public_key": point(&public_key) })
+    })
+    .collect();
+
+    let viewing_public_key = mul(&GENERATOR, &viewing_key);
+    let spending_public_key = mul(&GENERATOR, &spending_key);
+    let stealth_addresses: Vec<Value> = ephemeral_keys
+        .iter()
+        .map(|ephemeral_key| {
+            let shared_point = mul(&viewing_public_key, ephemeral_key);
+            let shared_secret = reduce(poseidon_hash(shared_point.x, shared_point.y));
+            let stealth_public_key = &spending_public_key + &mul(&GENERATOR, &shared_secret);
+            json!({
+                "ephemeral_key": hex(ephemeral_key),
+                "shared_point": point(&shared_point),
+                "shared_secret": hex(&shared_secret),
+                "stealth_public_key": point(&stealth_public_key),
+                "account_address": hex(&account_address(stealth_public_key.x, FieldElement::ZERO)),
+            })
+        })
+        .collect();
+
+    let mut accounts = Vec::new();
+    for public_key in [viewing_public_key.x, spending_public_key.x] {
+        for deployer in [FieldElement::ZERO, felt(UNIVERSAL_DEPLOYER)] {
+            accounts.push(json!({
+                "class_hash": CLASS_HASH,
+                "deployer": hex(&deployer),
+                "public_key": hex(&public_key),
+                "address": hex(&account_address(public_key, deployer)),
+            }));
+        }
+    }
+
+    let fixture = json!({
+        "viewing_key": hex(&viewing_key),
+        "spending_key": hex(&spending_key),
+        "class_hash": CLASS_HASH,
+        "public_keys": public_keys,
+        "stealth_addresses": stealth_addresses,
+        "accounts": accounts,
+    });
+    println!("{}", serde_json::to_string_pretty(&fixture).unwrap());
+}
@@ -0,0 +1,140 @@
+//! Round-trips keys, meta-addresses and announcements on every curve through a human-readable
+//! format (JSON) and two binary ones (bincode and CBOR).
+#![cfg(feature = "serde")]
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use stealth_address_kit::prelude::*;
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Keys<C: StealthAddressOnCurve> {
+    #[serde(with = "stealth_address_kit::compressed")]
+    spending_key: SecretKey<C>,
+    #[serde(with = "stealth_address_kit::compressed")]
+    viewing_key: SecretKey<C>,
+}
+
+fn json<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+fn bincode<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    bincode::deserialize(&bincode::serialize(value).unwrap()).unwrap()
+}
+
+fn cbor<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    ciborium::from_reader(bytes.as_slice()).unwrap()
+}
+
+fn round_trip<C: StealthAddressOnCurve>()
+where
+    Fr<C>: HasViewTag,
+{
+    let (spending_key, spending_public_key) = C::random_keypair();
+    let (viewing_key, viewing_public_key) = C::random_keypair();
+    let meta_address = MetaAddress::<C>::new(spending_public_key, viewing_public_key);
+    let announcement = meta_address.generate_stealth_address(C::generate_random_fr());
+    let keys = Keys::<C> {
+        spending_key,
+        viewing_key,
+    };
+
+    for codec in [json, bincode, cbor] {
+        assert_eq!(codec(&meta_address), meta_address, "{}", C::NAME);
+    }
+    for codec in [json, bincode, cbor] {
+        assert_eq!(codec(&announcement), announcement, "{}", C::NAME);
+    }
+    for codec in [json, bincode, cbor] {
+        let decoded = codec(&keys);
+        assert_eq!(decoded.spending_key, spending_key, "{}", C::NAME);
+        assert_eq!(decoded.viewing_key, viewing_key, "{}", C::NAME);
+    }
+
+    let decoded = json(&announcement)
+        .stealth_private_key(viewing_key, spending_key)
+        .unwrap();
+    assert_eq!(C::derive_public_key(&decoded), announcement.stealth_address);
+}
+
+macro_rules! round_trip_tests {
+    ($($feature:literal => $name:ident: $Curve:ty,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            #[test]
+            fn $name() {
+                round_trip::<$Curve>();
+            }
+        )*
+    };
+}
+
+round_trip_tests! {
+    "baby_jub_jub" => baby_jub_jub: BabyJubJub,
+    "bandersnatch" => bandersnatch: Bandersnatch,
+    "bls12_377" => bls12_377: Bls12_377,
+    "bls12_377" => bls12_377_g2: Bls12_377G2,
+    "bls12_381" => bls12_381: Bls12_381,
+    "bls12_381" => bls12_381_g2: Bls12_381G2,
+    "bn254" => bn254: Bn254,
+    "bn254" => bn254_g2: Bn254G2,
+    "bw6_761" => bw6_761: BW6_761,
+    "bw6_761" => bw6_761_g2: BW6_761G2,
+    "ed25519" => ed25519: Ed25519,
+    "ed_on_bls12_377" => ed_on_bls12_377: EdOnBls12_377,
+    "grumpkin" => grumpkin: Grumpkin,
+    "jubjub" => jubjub: Jubjub,
+    "pallas" => pallas: Pallas,
+    "ristretto255" => ristretto255: Ristretto255,
+    "secp256k1" => secp256k1: Secp256k1,
+    "secp256r1" => secp256r1: Secp256r1,
+    "secq256k1" => secq256k1: Secq256k1,
+    "stark_curve" => stark_curve: StarkCurve,
+    "vesta" => vesta: Vesta,
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn human_readable_formats_use_hex() {
+    let (_, spending_public_key) = Secp256k1::random_keypair();
+    let (_, viewing_public_key) = Secp256k1::random_keypair();
+    let meta_address = MetaAddress::<Secp256k1>::new(spending_public_key, viewing_public_key);
+
+    let value = serde_json::to_value(meta_address).unwrap();
+    let spending_public_key = value["spending_public_key"].as_str().unwrap().to_owned();
+    assert!(spending_public_key.starts_with("0x"));
+    assert_eq!(
+        spending_public_key.len(),
+        2 + 2 * Secp256k1::COMPRESSED_POINT_SIZE
+    );
+
+    // the prefix is optional, but the encoding must be canonical
+    let mut unprefixed = value.clone();
+    unprefixed["spending_public_key"] = spending_public_key[2..].into();
+    assert_eq!(
+        serde_json::from_value::<MetaAddress<Secp256k1>>(unprefixed).unwrap(),
+        meta_address
+    );
+    let mut malformed = value;
+    malformed["spending_public_key"] = format!("{spending_public_key}00").into();
+    assert!(serde_json::from_value::<MetaAddress<Secp256k1>>(malformed).is_err());
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn binary_formats_use_raw_bytes() {
+    let (_, spending_public_key) = Secp256k1::random_keypair();
+    let (_, viewing_public_key) = Secp256k1::random_keypair();
+    let meta_address = MetaAddress::<Secp256k1>::new(spending_public_key, viewing_public_key);
+
+    // two length-prefixed compressed points
+    let bytes = bincode::serialize(&meta_address).unwrap();
+    assert_eq!(bytes.len(), 2 * (8 + Secp256k1::COMPRESSED_POINT_SIZE));
+
+    let mut truncated = bytes.clone();
+    truncated[0] -= 1;
+    truncated.remove(8);
+    assert!(bincode::deserialize::<MetaAddress<Secp256k1>>(&truncated).is_err());
+}
@@ -0,0 +1,129 @@
+//! Checks the STARK curve, its Poseidon shared secrets and the Starknet account addresses against
+//! `fixtures/starknet.json`.
+//!
+//! The fixture is written by `fixtures/starknet_generator` with starknet-rs (starknet-crypto 0.6.2,
+//! starknet-curve 0.4.2 and starknet-core 0.6.1) rather than recorded on chain: the public keys
+//! with `get_public_key` and the points of starknet-curve, the shared secrets as the Poseidon hash
+//! of the coordinates of the shared point reduced by the order of the curve, and the account
+//! addresses with `get_contract_address`, for the class hash of an OpenZeppelin account, deployed
+//! by a `DEPLOY_ACCOUNT` transaction and by the Universal Deployer Contract.
+#![cfg(feature = "stark_curve")]
+
+use ark_ff::{BigInteger, Field, PrimeField};
+use serde_json::Value;
+use stealth_address_kit::prelude::*;
+use stealth_address_kit::starknet::{
+    felt_from_hex, felt_to_hex, public_key_felt, AccountClass, Felt,
+};
+
+fn fixture() -> Value {
+    serde_json::from_str(include_str!("fixtures/starknet.json")).unwrap()
+}
+
+fn felt(value: &Value) -> Felt {
+    felt_from_hex(value.as_str().unwrap()).unwrap()
+}
+
+fn scalar(value: &Value) -> Fr<StarkCurve> {
+    Fr::<StarkCurve>::from_be_bytes_mod_order(&felt(value).into_bigint().to_bytes_be())
+}
+
+fn point(value: &Value) -> PublicKey<StarkCurve> {
+    PublicKey::<StarkCurve>::new(felt(&value["x"]), felt(&value["y"]), Felt::ONE)
+}
+
+#[test]
+fn derives_public_keys_as_starknet_does() {
+    for public_key in fixture()["public_keys"].as_array().unwrap() {
+        assert_eq!(
+            StarkCurve::derive_public_key(&scalar(&public_key["private_key"])),
+            point(&public_key["public_key"])
+        );
+    }
+}
+
+#[test]
+fn pays_stealth_addresses_into_starknet_accounts() {
+    let fixture = fixture();
+    let account_class = AccountClass::new(felt(&fixture["class_hash"]));
+
+    for stealth_address in fixture["stealth_addresses"].as_array().unwrap() {
+        assert_eq!(
+            StarkCurve::hash_shared_point(&point(&stealth_address["shared_point"])),
+            scalar(&stealth_address["shared_secret"])
+        );
+        assert_eq!(
+            account_class.account_address(&point(&stealth_address["stealth_public_key"])),
+            felt(&stealth_address["account_address"])
+        );
+    }
+}
+
+#[test]
+fn generates_stealth_addresses_with_poseidon_shared_secrets() {
+    let fixture = fixture();
+    let viewing_key = scalar(&fixture["viewing_key"]);
+    let spending_key = scalar(&fixture["spending_key"]);
+    let viewing_public_key = StarkCurve::derive_public_key(&viewing_key);
+    let spending_public_key = StarkCurve::derive_public_key(&spending_key);
+
+    for stealth_address in fixture["stealth_addresses"].as_array().unwrap() {
+        let ephemeral_key = scalar(&stealth_address["ephemeral_key"]);
+        let shared_point = point(&stealth_address["shared_point"]);
+        assert_eq!(viewing_public_key * ephemeral_key, shared_point);
+        assert_eq!(
+            StarkCurve::hash_shared_point(&shared_point),
+            scalar(&stealth_address["shared_secret"])
+        );
+
+        let (stealth_public_key, view_tag) = StarkCurve::generate_stealth_address(
+            viewing_public_key,
+            spending_public_key,
+            ephemeral_key,
+        );
+        assert_eq!(
+            stealth_public_key,
+            point(&stealth_address["stealth_public_key"])
+        );
+
+        let stealth_private_key = StarkCurve::generate_stealth_private_key(
+            StarkCurve::derive_public_key(&ephemeral_key),
+            viewing_key,
+            spending_key,
+            view_tag,
+        )
+        .unwrap();
+        assert_eq!(
+            StarkCurve::derive_public_key(&stealth_private_key),
+            stealth_public_key
+        );
+    }
+}
+
+#[test]
+fn computes_account_addresses_as_starknet_does() {
+    let fixture = fixture();
+    let public_keys = fixture["public_keys"].as_array().unwrap();
+
+    for account in fixture["accounts"].as_array().unwrap() {
+        let public_key = public_keys
+            .iter()
+            .map(|public_key| point(&public_key["public_key"]))
+            .find(|public_key| felt_to_hex(&public_key_felt(public_key)) == account["public_key"])
+            .unwrap();
+        let account_class = AccountClass {
+            class_hash: felt(&account["class_hash"]),
+            deployer: felt(&account["deployer"]),
+        };
+
+        assert_eq!(
+            account_class.account_address(&public_key),
+            felt(&account["address"])
+        );
+        #[cfg(feature = "chain_address")]
+        {
+            use stealth_address_kit::chain_address::ChainAddressFormat;
+            assert_eq!(account_class.address(&public_key), account["address"]);
+        }
+    }
+}
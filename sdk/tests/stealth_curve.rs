@@ -0,0 +1,76 @@
+//! Plugs a curve in from outside the sdk, the way a downstream crate would, through
+//! `StealthCurve` and the exported macros.
+#![cfg(all(feature = "secp256k1", feature = "ffi"))]
+
+use ark_secp256k1::{Fr, Projective};
+use stealth_address_kit::{
+    define_curve_ffi, define_curve_tests, Secp256k1, StealthAddressOnCurve, StealthCurve,
+};
+
+type Custom = StealthCurve<Projective>;
+
+define_curve_ffi!(custom, Custom, Fr, Projective, 32, 33);
+define_curve_tests!(Custom);
+
+/// Curves without a standard point encoding hash the shared point as the wrapper does.
+#[cfg(feature = "bn254")]
+#[test]
+fn matches_the_dedicated_impl() {
+    use stealth_address_kit::Bn254;
+
+    type CustomBn254 = StealthCurve<ark_bn254::G1Projective>;
+
+    let (spending_key, spending_public_key) = Bn254::random_keypair();
+    let (viewing_key, viewing_public_key) = Bn254::random_keypair();
+    let (ephemeral_private_key, ephemeral_public_key) = Bn254::random_keypair();
+
+    let stealth_address = CustomBn254::generate_stealth_address(
+        viewing_public_key,
+        spending_public_key,
+        ephemeral_private_key,
+    );
+    assert_eq!(
+        stealth_address,
+        Bn254::generate_stealth_address(
+            viewing_public_key,
+            spending_public_key,
+            ephemeral_private_key,
+        )
+    );
+    assert_eq!(
+        CustomBn254::generate_stealth_private_key(
+            ephemeral_public_key,
+            viewing_key,
+            spending_key,
+            stealth_address.1,
+        ),
+        Bn254::generate_stealth_private_key(
+            ephemeral_public_key,
+            viewing_key,
+            spending_key,
+            stealth_address.1,
+        )
+    );
+}
+
+/// The wrapper encodes points as arkworks does, not in the SEC1 format of `Secp256k1`.
+#[test]
+fn encodes_points_as_arkworks_does() {
+    use ark_serialize::CanonicalSerialize;
+
+    let (_, public_key) = Custom::random_keypair();
+    let mut encoded = Vec::new();
+    public_key.serialize_compressed(&mut encoded).unwrap();
+    assert_eq!(Custom::encode_point(&public_key), encoded);
+    assert_ne!(Secp256k1::encode_point(&public_key), encoded);
+}
+
+#[test]
+fn exports_ffi_functions() {
+    let keypair = custom_ffi_random_keypair();
+    assert_eq!(
+        unsafe { &*keypair }.err_code,
+        stealth_address_kit::CErrorCode::NoError
+    );
+    drop_custom_ffi_random_keypair(keypair);
+}
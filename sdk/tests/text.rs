@@ -0,0 +1,209 @@
+//! Round-trips keys and meta-addresses on every curve through the text formats, and checks that
+//! mistyped text, or text for another curve or kind of key, is rejected.
+#![cfg(feature = "text")]
+
+use stealth_address_kit::prelude::*;
+use stealth_address_kit::text::{self, ParseError, TextFormat};
+
+const FORMATS: [TextFormat; 3] = [
+    TextFormat::Hex,
+    TextFormat::Bech32m,
+    TextFormat::Base58Check,
+];
+
+fn round_trip<C: StealthAddressOnCurve>() {
+    let (spending_key, spending_public_key) = C::random_keypair();
+    let (_, viewing_public_key) = C::random_keypair();
+    let meta_address = MetaAddress::<C>::new(spending_public_key, viewing_public_key);
+    let public_key = text::PublicKey::<C>(spending_public_key);
+    let secret_key = text::SecretKey::<C>(spending_key);
+
+    for format in FORMATS {
+        let parsed = meta_address.to_text(format).parse::<MetaAddress<C>>();
+        assert_eq!(parsed, Ok(meta_address), "{} {format:?}", C::NAME);
+        let parsed = public_key.to_text(format).parse::<text::PublicKey<C>>();
+        assert_eq!(parsed, Ok(public_key), "{} {format:?}", C::NAME);
+        let parsed = secret_key.to_text(format).parse::<text::SecretKey<C>>();
+        assert_eq!(parsed, Ok(secret_key), "{} {format:?}", C::NAME);
+    }
+    assert_eq!(
+        meta_address.to_string(),
+        meta_address.to_text(TextFormat::Bech32m)
+    );
+}
+
+macro_rules! round_trip_tests {
+    ($($feature:literal => $name:ident: $Curve:ty,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            #[test]
+            fn $name() {
+                round_trip::<$Curve>();
+            }
+        )*
+    };
+}
+
+round_trip_tests! {
+    "baby_jub_jub" => baby_jub_jub: BabyJubJub,
+    "bandersnatch" => bandersnatch: Bandersnatch,
+    "bls12_377" => bls12_377: Bls12_377,
+    "bls12_377" => bls12_377_g2: Bls12_377G2,
+    "bls12_381" => bls12_381: Bls12_381,
+    "bls12_381" => bls12_381_g2: Bls12_381G2,
+    "bn254" => bn254: Bn254,
+    "bn254" => bn254_g2: Bn254G2,
+    "bw6_761" => bw6_761: BW6_761,
+    "bw6_761" => bw6_761_g2: BW6_761G2,
+    "ed25519" => ed25519: Ed25519,
+    "ed_on_bls12_377" => ed_on_bls12_377: EdOnBls12_377,
+    "grumpkin" => grumpkin: Grumpkin,
+    "jubjub" => jubjub: Jubjub,
+    "pallas" => pallas: Pallas,
+    "ristretto255" => ristretto255: Ristretto255,
+    "secp256k1" => secp256k1: Secp256k1,
+    "secp256r1" => secp256r1: Secp256r1,
+    "secq256k1" => secq256k1: Secq256k1,
+    "stark_curve" => stark_curve: StarkCurve,
+    "vesta" => vesta: Vesta,
+}
+
+#[cfg(feature = "secp256k1")]
+fn meta_address() -> MetaAddress<Secp256k1> {
+    let (_, spending_public_key) = Secp256k1::random_keypair();
+    let (_, viewing_public_key) = Secp256k1::random_keypair();
+    MetaAddress::new(spending_public_key, viewing_public_key)
+}
+
+/// Replaces the character at the index with another one from the same alphabet.
+fn mistype(text: &str, index: usize, alphabet: &str) -> String {
+    let original = text.as_bytes()[index] as char;
+    let replacement = alphabet.chars().find(|c| *c != original).unwrap();
+    let mut mistyped = text.to_owned();
+    mistyped.replace_range(index..=index, &replacement.to_string());
+    mistyped
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn mistyped_text_is_rejected() {
+    let meta_address = meta_address();
+    let bech32m = meta_address.to_text(TextFormat::Bech32m);
+    let base58 = meta_address.to_text(TextFormat::Base58Check);
+    let hex = meta_address.to_text(TextFormat::Hex);
+
+    let mistyped = mistype(&bech32m, bech32m.len() - 10, "qp");
+    assert_eq!(
+        mistyped.parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+    let mistyped = mistype(&base58, base58.len() / 2, "23");
+    assert_eq!(
+        mistyped.parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+
+    // flipping the case of a letter breaks the mixed-case checksum
+    let index = hex
+        .rfind(|c: char| c.is_ascii_alphabetic() && c != 'x')
+        .unwrap();
+    let mut flipped = hex.clone();
+    let letter = hex.as_bytes()[index] as char;
+    let letter = if letter.is_ascii_uppercase() {
+        letter.to_ascii_lowercase()
+    } else {
+        letter.to_ascii_uppercase()
+    };
+    flipped.replace_range(index..=index, &letter.to_string());
+    assert_eq!(
+        flipped.parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+    // and the checksum is required
+    assert_eq!(
+        hex.to_lowercase().parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+    assert_eq!(
+        format!("0x{}", hex[2..].to_uppercase()).parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+
+    assert!(matches!(
+        "0xzz".parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::Malformed(_))
+    ));
+    assert!(matches!(
+        "st_secp256k11".parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::Malformed(_))
+    ));
+    assert!(matches!(
+        "0OIl".parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::Malformed(_))
+    ));
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn text_for_another_kind_of_key_is_rejected() {
+    let meta_address = meta_address();
+    let public_key = text::PublicKey::<Secp256k1>(meta_address.spending_public_key);
+
+    assert_eq!(
+        public_key.to_string().parse::<text::SecretKey<Secp256k1>>(),
+        Err(ParseError::WrongPrefix {
+            expected: "sk_secp256k1".to_owned(),
+            found: Some("pk_secp256k1".to_owned()),
+        })
+    );
+    assert_eq!(
+        public_key
+            .to_text(TextFormat::Base58Check)
+            .parse::<MetaAddress<Secp256k1>>(),
+        Err(ParseError::WrongPrefix {
+            expected: "st_secp256k1".to_owned(),
+            found: None,
+        })
+    );
+    // hex does not name the kind, but binds it in its checksum
+    assert_eq!(
+        public_key
+            .to_text(TextFormat::Hex)
+            .parse::<text::SecretKey<Secp256k1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+}
+
+#[cfg(all(feature = "secp256k1", feature = "secp256r1"))]
+#[test]
+fn text_for_another_curve_is_rejected() {
+    let meta_address = meta_address();
+
+    assert_eq!(
+        meta_address.to_string().parse::<MetaAddress<Secp256r1>>(),
+        Err(ParseError::WrongPrefix {
+            expected: "st_secp256r1".to_owned(),
+            found: Some("st_secp256k1".to_owned()),
+        })
+    );
+    assert!(matches!(
+        meta_address
+            .to_text(TextFormat::Base58Check)
+            .parse::<MetaAddress<Secp256r1>>(),
+        Err(ParseError::WrongPrefix { found: None, .. })
+    ));
+    assert_eq!(
+        meta_address
+            .to_text(TextFormat::Hex)
+            .parse::<MetaAddress<Secp256r1>>(),
+        Err(ParseError::InvalidChecksum)
+    );
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn secret_keys_are_redacted_from_debug_output() {
+    let (secret_key, _) = Secp256k1::random_keypair();
+    let secret_key = text::SecretKey::<Secp256k1>(secret_key);
+    assert_eq!(format!("{secret_key:?}"), "SecretKey(..)");
+}
@@ -0,0 +1,194 @@
+//! Round-trips signed payment requests on every curve through their `stealth:` URIs, and checks
+//! that tampered, malformed or unsupported requests are rejected.
+#![cfg(feature = "text")]
+
+use stealth_address_kit::prelude::*;
+use stealth_address_kit::text::TextFormat;
+use stealth_address_kit::uri::{PaymentRequest, UriError};
+
+fn signed_round_trip<C: StealthAddressOnCurve>()
+where
+    Fr<C>: HasViewTag,
+{
+    let (spending_key, spending_public_key) = C::random_keypair();
+    let (_, viewing_public_key) = C::random_keypair();
+    let request = PaymentRequest::new(MetaAddress::<C>::new(
+        spending_public_key,
+        viewing_public_key,
+    ))
+    .with_chain_id(10)
+    .with_amount("12.5")
+    .unwrap()
+    .with_memo("coffee & cake")
+    .with_expiry(1_767_225_600);
+
+    let uri = request.to_signed_uri(&spending_key);
+    assert_eq!(
+        PaymentRequest::<C>::from_signed_uri(&uri),
+        Ok(request.clone()),
+        "{}",
+        C::NAME
+    );
+    // only the spending key can sign for the meta-address
+    let (other_key, _) = C::random_keypair();
+    assert_eq!(
+        PaymentRequest::<C>::from_uri(&request.to_signed_uri(&other_key)),
+        Err(UriError::InvalidSignature),
+        "{}",
+        C::NAME
+    );
+}
+
+macro_rules! round_trip_tests {
+    ($($feature:literal => $name:ident: $Curve:ty,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            #[test]
+            fn $name() {
+                signed_round_trip::<$Curve>();
+            }
+        )*
+    };
+}
+
+round_trip_tests! {
+    "baby_jub_jub" => baby_jub_jub: BabyJubJub,
+    "bandersnatch" => bandersnatch: Bandersnatch,
+    "bls12_377" => bls12_377: Bls12_377,
+    "bls12_377" => bls12_377_g2: Bls12_377G2,
+    "bls12_381" => bls12_381: Bls12_381,
+    "bls12_381" => bls12_381_g2: Bls12_381G2,
+    "bn254" => bn254: Bn254,
+    "bn254" => bn254_g2: Bn254G2,
+    "bw6_761" => bw6_761: BW6_761,
+    "bw6_761" => bw6_761_g2: BW6_761G2,
+    "ed25519" => ed25519: Ed25519,
+    "ed_on_bls12_377" => ed_on_bls12_377: EdOnBls12_377,
+    "grumpkin" => grumpkin: Grumpkin,
+    "jubjub" => jubjub: Jubjub,
+    "pallas" => pallas: Pallas,
+    "ristretto255" => ristretto255: Ristretto255,
+    "secp256k1" => secp256k1: Secp256k1,
+    "secp256r1" => secp256r1: Secp256r1,
+    "secq256k1" => secq256k1: Secq256k1,
+    "stark_curve" => stark_curve: StarkCurve,
+    "vesta" => vesta: Vesta,
+}
+
+#[cfg(feature = "secp256k1")]
+fn keys() -> (Fr<Secp256k1>, MetaAddress<Secp256k1>) {
+    let (spending_key, spending_public_key) = Secp256k1::random_keypair();
+    let (_, viewing_public_key) = Secp256k1::random_keypair();
+    (
+        spending_key,
+        MetaAddress::new(spending_public_key, viewing_public_key),
+    )
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn builds_the_documented_uri() {
+    let (_, meta_address) = keys();
+    let request = PaymentRequest::new(meta_address)
+        .with_chain_id(1)
+        .with_amount("0.5")
+        .unwrap()
+        .with_asset("ETH")
+        .with_memo("Invoice 42 / März")
+        .with_expiry(1_767_225_600);
+
+    assert_eq!(
+        request.to_uri(),
+        format!(
+            "stealth:{meta_address}?scheme=1&chain_id=1&amount=0.5&asset=ETH\
+             &memo=Invoice%2042%20%2F%20M%C3%A4rz&expiry=1767225600"
+        )
+    );
+    assert_eq!(request.to_uri().parse(), Ok(request.clone()));
+    assert!(request.is_expired_at(1_767_225_601));
+    assert!(!request.is_expired_at(1_767_225_600));
+
+    // a bare meta-address is a request too, in any of the text formats
+    let bare = format!("STEALTH:{}", meta_address.to_text(TextFormat::Hex));
+    assert_eq!(bare.parse(), Ok(PaymentRequest::new(meta_address)));
+    assert_eq!(
+        PaymentRequest::<Secp256k1>::from_signed_uri(&bare),
+        Err(UriError::MissingSignature)
+    );
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn tampered_requests_are_rejected() {
+    let (spending_key, meta_address) = keys();
+    let uri = PaymentRequest::new(meta_address)
+        .with_amount("1")
+        .unwrap()
+        .to_signed_uri(&spending_key);
+
+    let tampered = uri.replace("amount=1", "amount=100");
+    assert_eq!(
+        PaymentRequest::<Secp256k1>::from_uri(&tampered),
+        Err(UriError::InvalidSignature)
+    );
+    // the signature must cover every parameter
+    let appended = format!("{uri}&memo=refund");
+    assert_eq!(
+        PaymentRequest::<Secp256k1>::from_uri(&appended),
+        Err(UriError::InvalidSignature)
+    );
+    let truncated = &uri[..uri.len() - 2];
+    assert_eq!(
+        PaymentRequest::<Secp256k1>::from_uri(truncated),
+        Err(UriError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn malformed_requests_are_rejected() {
+    let (_, meta_address) = keys();
+    let parse = |query: &str| {
+        format!("stealth:{meta_address}?{query}").parse::<PaymentRequest<Secp256k1>>()
+    };
+
+    assert_eq!(
+        parse("amount=1e3"),
+        Err(UriError::InvalidParameter("amount".to_owned()))
+    );
+    assert_eq!(
+        parse("amount=.5"),
+        Err(UriError::InvalidParameter("amount".to_owned()))
+    );
+    assert_eq!(
+        parse("scheme=2"),
+        Err(UriError::InvalidParameter("scheme".to_owned()))
+    );
+    assert_eq!(
+        parse("memo=%E2%82"),
+        Err(UriError::InvalidParameter("memo".to_owned()))
+    );
+    assert_eq!(
+        parse("chain_id=1&chain_id=2"),
+        Err(UriError::DuplicateParameter("chain_id".to_owned()))
+    );
+    assert_eq!(
+        parse("req-refund_to=0x00"),
+        Err(UriError::UnsupportedRequirement("req-refund_to".to_owned()))
+    );
+    // unknown parameters that are not required are ignored
+    assert_eq!(parse("label=shop"), Ok(PaymentRequest::new(meta_address)));
+
+    assert_eq!(
+        PaymentRequest::<Secp256k1>::from_uri(&format!("ethereum:{meta_address}")),
+        Err(UriError::NotAStealthUri)
+    );
+    assert!(matches!(
+        PaymentRequest::<Secp256k1>::from_uri("stealth:st_secp256k11"),
+        Err(UriError::MetaAddress(_))
+    ));
+    assert_eq!(
+        PaymentRequest::new(meta_address).with_amount("-1"),
+        Err(UriError::InvalidParameter("amount".to_owned()))
+    );
+}
@@ -0,0 +1,106 @@
+//! A sidecar daemon serving the StealthAddressOnCurve operations of every compiled-in curve
+//! over line-delimited JSON-RPC 2.0, on stdin/stdout or on a unix socket.
+mod rpc;
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: stealth-address-sidecar [--stdio | --socket <path>]";
+
+fn serve<R: BufRead, W: Write>(reader: R, mut writer: W) -> std::io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = rpc::handle_line(&line) {
+            writeln!(writer, "{response}")?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes the socket file a sidecar left behind when it was killed, so that binding does not
+/// fail with `AddrInUse`. A socket that still accepts connections, or a file that is not a
+/// socket, is left alone and reported instead.
+#[cfg(unix)]
+fn remove_stale_socket(path: &str) -> std::io::Result<()> {
+    use std::io::{Error, ErrorKind};
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixStream;
+
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{path} already exists and is not a socket"),
+        ));
+    }
+    if UnixStream::connect(path).is_ok() {
+        return Err(Error::new(
+            ErrorKind::AddrInUse,
+            format!("{path} is already served by another process"),
+        ));
+    }
+    std::fs::remove_file(path)
+}
+
+#[cfg(unix)]
+fn serve_socket(path: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixListener;
+
+    remove_stale_socket(path)?;
+    let listener = UnixListener::bind(path)?;
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("failed to accept connection: {err}");
+                continue;
+            }
+        };
+        std::thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(err) => return eprintln!("failed to clone connection: {err}"),
+            };
+            if let Err(err) = serve(reader, stream) {
+                eprintln!("connection closed: {err}");
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_path: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "unix sockets are not supported on this platform",
+    ))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] | ["--stdio"] => serve(std::io::stdin().lock(), std::io::stdout().lock()),
+        ["--socket", path] => serve_socket(path),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use serde_json::{json, Map, Value};
+use stealth_address_kit::{
+    curves, find_curve, DynStealthCurve, InvalidEncoding, InvalidMetaAddress,
+};
+
+// JSON-RPC 2.0 error codes
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+// application error codes
+pub const UNSUPPORTED_CURVE: i64 = -32000;
+pub const INVALID_ENCODING: i64 = -32001;
+pub const VIEW_TAG_MISMATCH: i64 = -32002;
+
+/// The chain prefix used in meta-addresses when none is given.
+const DEFAULT_CHAIN: &str = "eth";
+
+#[derive(Debug)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        RpcError::new(INVALID_PARAMS, message)
+    }
+
+    fn invalid_encoding(field: &str) -> impl FnOnce(InvalidEncoding) -> Self + '_ {
+        move |_| {
+            RpcError::new(
+                INVALID_ENCODING,
+                format!("`{field}` is not a canonical encoding for the curve"),
+            )
+        }
+    }
+}
+
+/// Handles one line of input, returning the serialized response.
+/// Notifications (requests without an id) get no response. Batches are not supported, so like
+/// any other json that is not a request object, they get an invalid request error.
+pub fn handle_line(line: &str) -> Option<String> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(response(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, err.to_string())),
+            ))
+        }
+    };
+    if !request.is_object() {
+        return Some(response(
+            Value::Null,
+            Err(RpcError::new(INVALID_REQUEST, "expected a request object")),
+        ));
+    }
+
+    let id = request.get("id").cloned();
+    let result = dispatch(&request);
+    id.map(|id| response(id, result))
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": err.code, "message": err.message },
+        }),
+    }
+    .to_string()
+}
+
+fn dispatch(request: &Value) -> Result<Value, RpcError> {
+    if request.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(RpcError::new(INVALID_REQUEST, "expected jsonrpc 2.0"));
+    }
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_REQUEST, "missing method"))?;
+    let empty = Map::new();
+    let params = match request.get("params") {
+        None => Params(&empty),
+        Some(Value::Object(params)) => Params(params),
+        Some(_) => return Err(RpcError::invalid_params("params must be an object")),
+    };
+
+    match method {
+        "curves" => Ok(json!(curves()
+            .iter()
+            .map(|curve| curve.name())
+            .collect::<Vec<_>>())),
+        "keygen" => keygen(params),
+        "derive_public_key" => derive_public_key(params),
+        "encode_meta_address" => encode_meta_address(params),
+        "decode_meta_address" => decode_meta_address(params),
+        "send" => send(params),
+        "check_announcement" => check_announcement(params),
+        "scan" => scan(params),
+        "derive_stealth_private_key" => derive_stealth_private_key(params),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method `{method}`"),
+        )),
+    }
+}
+
+fn keygen(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let (private_key, public_key) = curve.random_keypair();
+    Ok(json!({ "private_key": to_hex(&private_key), "public_key": to_hex(&public_key) }))
+}
+
+fn derive_public_key(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let public_key = curve
+        .derive_public_key(&params.bytes("private_key")?)
+        .map_err(RpcError::invalid_encoding("private_key"))?;
+    Ok(json!({ "public_key": to_hex(&public_key) }))
+}
+
+fn encode_meta_address(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let chain = params.optional_str("chain")?.unwrap_or(DEFAULT_CHAIN);
+    let spending_public_key = params.public_key(curve, "spending_public_key")?;
+    let viewing_public_key = params.public_key(curve, "viewing_public_key")?;
+
+    Ok(json!({
+        "meta_address": format!(
+            "st:{chain}:0x{}{}",
+            hex::encode(spending_public_key),
+            hex::encode(viewing_public_key)
+        )
+    }))
+}
+
+fn decode_meta_address(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let (chain, spending_public_key, viewing_public_key) = params.meta_address(curve)?;
+    Ok(json!({
+        "chain": chain,
+        "spending_public_key": to_hex(&spending_public_key),
+        "viewing_public_key": to_hex(&viewing_public_key),
+    }))
+}
+
+fn send(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let (_, spending_public_key, viewing_public_key) = params.meta_address(curve)?;
+    let (ephemeral_private_key, ephemeral_public_key) = curve.random_keypair();
+    let (stealth_address, view_tag) = curve
+        .generate_stealth_address(
+            &viewing_public_key,
+            &spending_public_key,
+            &ephemeral_private_key,
+        )
+        .map_err(RpcError::invalid_encoding("meta_address"))?;
+    Ok(json!({
+        "stealth_address": to_hex(&stealth_address),
+        "ephemeral_public_key": to_hex(&ephemeral_public_key),
+        "view_tag": view_tag,
+    }))
+}
+
+fn check_announcement(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let stealth_address = params.bytes("stealth_address")?;
+    let expected = curve
+        .check_stealth_address(
+            &params.bytes("ephemeral_public_key")?,
+            &params.bytes("viewing_key")?,
+            &params.bytes("spending_public_key")?,
+            params.view_tag("view_tag")?,
+        )
+        .map_err(RpcError::invalid_encoding("announcement"))?;
+    Ok(json!({ "matches": expected == Some(stealth_address) }))
+}
+
+fn scan(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let announcements = params
+        .array("announcements")?
+        .iter()
+        .map(|announcement| {
+            let announcement = match announcement {
+                Value::Object(announcement) => Params(announcement),
+                _ => return Err(RpcError::invalid_params("announcements must be objects")),
+            };
+            Ok((
+                announcement.bytes("stealth_address")?,
+                announcement.bytes("ephemeral_public_key")?,
+                announcement.view_tag("view_tag")?,
+            ))
+        })
+        .collect::<Result<Vec<_>, RpcError>>()?;
+
+    let found = curve
+        .scan(
+            &announcements,
+            &params.bytes("viewing_key")?,
+            &params.bytes("spending_key")?,
+        )
+        .map_err(RpcError::invalid_encoding("announcements"))?;
+    Ok(json!(found
+        .iter()
+        .map(|(index, stealth_private_key)| json!({
+            "index": index,
+            "stealth_private_key": to_hex(stealth_private_key),
+        }))
+        .collect::<Vec<_>>()))
+}
+
+fn derive_stealth_private_key(params: Params) -> Result<Value, RpcError> {
+    let curve = params.curve()?;
+    let stealth_private_key = curve
+        .generate_stealth_private_key(
+            &params.bytes("ephemeral_public_key")?,
+            &params.bytes("viewing_key")?,
+            &params.bytes("spending_key")?,
+            params.view_tag("view_tag")?,
+        )
+        .map_err(RpcError::invalid_encoding("keys"))?
+        .ok_or_else(|| RpcError::new(VIEW_TAG_MISMATCH, "view tag does not match"))?;
+    Ok(json!({ "stealth_private_key": to_hex(&stealth_private_key) }))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).ok()
+}
+
+#[derive(Clone, Copy)]
+struct Params<'a>(&'a Map<String, Value>);
+
+impl<'a> Params<'a> {
+    fn str(&self, name: &str) -> Result<&'a str, RpcError> {
+        self.optional_str(name)?
+            .ok_or_else(|| RpcError::invalid_params(format!("missing `{name}`")))
+    }
+
+    fn optional_str(&self, name: &str) -> Result<Option<&'a str>, RpcError> {
+        match self.0.get(name) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(_) => Err(RpcError::invalid_params(format!(
+                "`{name}` must be a string"
+            ))),
+        }
+    }
+
+    fn array(&self, name: &str) -> Result<&'a Vec<Value>, RpcError> {
+        self.0
+            .get(name)
+            .and_then(Value::as_array)
+            .ok_or_else(|| RpcError::invalid_params(format!("`{name}` must be an array")))
+    }
+
+    fn curve(&self) -> Result<&'static dyn DynStealthCurve, RpcError> {
+        let name = self.str("curve")?;
+        find_curve(name).ok_or_else(|| {
+            RpcError::new(
+                UNSUPPORTED_CURVE,
+                format!("curve `{name}` is not compiled in"),
+            )
+        })
+    }
+
+    fn bytes(&self, name: &str) -> Result<Vec<u8>, RpcError> {
+        from_hex(self.str(name)?)
+            .ok_or_else(|| RpcError::invalid_params(format!("`{name}` must be a hex string")))
+    }
+
+    fn public_key(&self, curve: &dyn DynStealthCurve, name: &str) -> Result<Vec<u8>, RpcError> {
+        let bytes = self.bytes(name)?;
+        curve
+            .check_public_key(&bytes)
+            .map_err(RpcError::invalid_encoding(name))?;
+        Ok(bytes)
+    }
+
+    /// Accepts a json number, or a decimal or 0x-prefixed hex string, since javascript
+    /// clients cannot represent every u64 as a number.
+    fn view_tag(&self, name: &str) -> Result<u64, RpcError> {
+        let invalid = || RpcError::invalid_params(format!("`{name}` must be a u64"));
+        match self.0.get(name) {
+            Some(Value::Number(value)) => value.as_u64().ok_or_else(invalid),
+            Some(Value::String(value)) => match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).map_err(|_| invalid()),
+                None => value.parse().map_err(|_| invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+
+    fn meta_address(
+        &self,
+        curve: &dyn DynStealthCurve,
+    ) -> Result<(&'a str, Vec<u8>, Vec<u8>), RpcError> {
+        curve
+            .parse_meta_address(self.str("meta_address")?)
+            .map_err(|err| match err {
+                InvalidMetaAddress::Malformed => {
+                    RpcError::invalid_params("`meta_address` must look like st:<chain>:0x<keys>")
+                }
+                InvalidMetaAddress::InvalidEncoding => {
+                    RpcError::invalid_encoding("meta_address")(InvalidEncoding)
+                }
+            })
+    }
+}
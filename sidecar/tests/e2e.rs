@@ -0,0 +1,364 @@
+//! End-to-end tests, spawning the sidecar binary and talking JSON-RPC to it.
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const BIN: &str = env!("CARGO_BIN_EXE_stealth-address-sidecar");
+
+struct Sidecar {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Sidecar {
+    fn spawn() -> Self {
+        let mut child = Command::new(BIN)
+            .arg("--stdio")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Sidecar {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        }
+    }
+
+    fn send_line(&mut self, line: &str) -> Value {
+        writeln!(self.stdin, "{line}").unwrap();
+        let mut response = String::new();
+        self.stdout.read_line(&mut response).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        let response = self.send_line(
+            &json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string(),
+        );
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Value {
+        let response = self.request(method, params);
+        assert!(response.get("error").is_none(), "{response}");
+        response["result"].clone()
+    }
+
+    fn call_err(&mut self, method: &str, params: Value) -> i64 {
+        let response = self.request(method, params);
+        response["error"]["code"].as_i64().unwrap()
+    }
+}
+
+impl Drop for Sidecar {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn round_trip_on_every_curve() {
+    let mut sidecar = Sidecar::spawn();
+    let curves = sidecar.call("curves", json!({}));
+    assert!(!curves.as_array().unwrap().is_empty());
+
+    for curve in curves.as_array().unwrap() {
+        let spending = sidecar.call("keygen", json!({ "curve": curve }));
+        let viewing = sidecar.call("keygen", json!({ "curve": curve }));
+        assert_eq!(
+            sidecar.call(
+                "derive_public_key",
+                json!({ "curve": curve, "private_key": spending["private_key"] })
+            )["public_key"],
+            spending["public_key"]
+        );
+
+        let meta_address = sidecar.call(
+            "encode_meta_address",
+            json!({
+                "curve": curve,
+                "spending_public_key": spending["public_key"],
+                "viewing_public_key": viewing["public_key"],
+            }),
+        )["meta_address"]
+            .clone();
+        assert!(meta_address.as_str().unwrap().starts_with("st:eth:0x"));
+        let decoded = sidecar.call(
+            "decode_meta_address",
+            json!({ "curve": curve, "meta_address": meta_address }),
+        );
+        assert_eq!(decoded["chain"], "eth");
+        assert_eq!(decoded["spending_public_key"], spending["public_key"]);
+        assert_eq!(decoded["viewing_public_key"], viewing["public_key"]);
+
+        let announcement = sidecar.call(
+            "send",
+            json!({ "curve": curve, "meta_address": meta_address }),
+        );
+
+        // view-only check, without the spending key
+        let check = sidecar.call(
+            "check_announcement",
+            json!({
+                "curve": curve,
+                "stealth_address": announcement["stealth_address"],
+                "ephemeral_public_key": announcement["ephemeral_public_key"],
+                "view_tag": announcement["view_tag"],
+                "viewing_key": viewing["private_key"],
+                "spending_public_key": spending["public_key"],
+            }),
+        );
+        assert_eq!(check["matches"], true);
+        let check = sidecar.call(
+            "check_announcement",
+            json!({
+                "curve": curve,
+                "stealth_address": spending["public_key"],
+                "ephemeral_public_key": announcement["ephemeral_public_key"],
+                "view_tag": announcement["view_tag"],
+                "viewing_key": viewing["private_key"],
+                "spending_public_key": spending["public_key"],
+            }),
+        );
+        assert_eq!(check["matches"], false);
+
+        let stealth_private_key = sidecar.call(
+            "derive_stealth_private_key",
+            json!({
+                "curve": curve,
+                "ephemeral_public_key": announcement["ephemeral_public_key"],
+                // view tags may also be given as strings
+                "view_tag": announcement["view_tag"].as_u64().unwrap().to_string(),
+                "viewing_key": viewing["private_key"],
+                "spending_key": spending["private_key"],
+            }),
+        )["stealth_private_key"]
+            .clone();
+        assert_eq!(
+            sidecar.call(
+                "derive_public_key",
+                json!({ "curve": curve, "private_key": stealth_private_key })
+            )["public_key"],
+            announcement["stealth_address"]
+        );
+
+        let other_meta_address = sidecar.call(
+            "encode_meta_address",
+            json!({
+                "curve": curve,
+                "spending_public_key": spending["public_key"],
+                "viewing_public_key": spending["public_key"],
+            }),
+        )["meta_address"]
+            .clone();
+        let other = sidecar.call(
+            "send",
+            json!({ "curve": curve, "meta_address": other_meta_address }),
+        );
+        let found = sidecar.call(
+            "scan",
+            json!({
+                "curve": curve,
+                "viewing_key": viewing["private_key"],
+                "spending_key": spending["private_key"],
+                "announcements": [other, announcement],
+            }),
+        );
+        assert_eq!(
+            found,
+            json!([{ "index": 1, "stealth_private_key": stealth_private_key }])
+        );
+    }
+}
+
+#[test]
+fn reports_errors() {
+    let mut sidecar = Sidecar::spawn();
+    let curve = sidecar.call("curves", json!({}))[0].clone();
+    let keypair = sidecar.call("keygen", json!({ "curve": curve }));
+
+    assert_eq!(sidecar.call_err("unknown", json!({})), -32601);
+    assert_eq!(sidecar.call_err("keygen", json!({})), -32602);
+    assert_eq!(
+        sidecar.call_err("keygen", json!({ "curve": "ed448" })),
+        -32000
+    );
+    assert_eq!(
+        sidecar.call_err(
+            "derive_public_key",
+            json!({ "curve": curve, "private_key": "0xzz" })
+        ),
+        -32602
+    );
+    assert_eq!(
+        sidecar.call_err(
+            "derive_public_key",
+            json!({ "curve": curve, "private_key": "0xffff" })
+        ),
+        -32001
+    );
+    assert_eq!(
+        sidecar.call_err(
+            "decode_meta_address",
+            json!({ "curve": curve, "meta_address": "st:eth:0x00" })
+        ),
+        -32001
+    );
+    // keys of the right length that are not points of the curve
+    let public_key = keypair["public_key"].as_str().unwrap();
+    let off_curve = format!("0x{}", "ff".repeat((public_key.len() - 2) / 2));
+    assert_eq!(
+        sidecar.call_err(
+            "encode_meta_address",
+            json!({
+                "curve": curve,
+                "spending_public_key": off_curve,
+                "viewing_public_key": public_key,
+            })
+        ),
+        -32001
+    );
+    assert_eq!(
+        sidecar.call_err(
+            "decode_meta_address",
+            json!({
+                "curve": curve,
+                "meta_address": format!("st:eth:{public_key}{}", &off_curve[2..]),
+            })
+        ),
+        -32001
+    );
+    assert_eq!(
+        sidecar.call_err(
+            "derive_stealth_private_key",
+            json!({
+                "curve": curve,
+                "ephemeral_public_key": keypair["public_key"],
+                "view_tag": 0,
+                "viewing_key": keypair["private_key"],
+                "spending_key": keypair["private_key"],
+            })
+        ),
+        -32002
+    );
+
+    let response = sidecar.send_line("not json");
+    assert_eq!(response["error"]["code"], -32700);
+    assert_eq!(response["id"], Value::Null);
+
+    // json that is not a request object, including batches, which are not supported
+    for line in [
+        "[]",
+        "1",
+        "\"curves\"",
+        "null",
+        r#"[{"jsonrpc":"2.0","id":1,"method":"curves"}]"#,
+    ] {
+        let response = sidecar.send_line(line);
+        assert_eq!(response["error"]["code"], -32600, "{line}");
+        assert_eq!(response["id"], Value::Null, "{line}");
+    }
+
+    // notifications get no response, so the next line answers the following request
+    writeln!(
+        sidecar.stdin,
+        "{}",
+        json!({ "jsonrpc": "2.0", "method": "curves" })
+    )
+    .unwrap();
+    assert_eq!(sidecar.call("curves", json!({}))[0], curve);
+}
+
+#[cfg(unix)]
+#[test]
+fn serves_unix_socket() {
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::time::{Duration, Instant};
+
+    let path = std::env::temp_dir().join(format!(
+        "stealth-address-sidecar-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    // the socket file of a sidecar that was killed, which nothing listens on anymore
+    drop(UnixListener::bind(&path).unwrap());
+    let mut child = Command::new(BIN)
+        .arg("--socket")
+        .arg(&path)
+        .spawn()
+        .unwrap();
+
+    let started = Instant::now();
+    let stream = loop {
+        match UnixStream::connect(&path) {
+            Ok(stream) => break stream,
+            Err(_) if started.elapsed() < Duration::from_secs(10) => {
+                std::thread::sleep(Duration::from_millis(20))
+            }
+            Err(err) => panic!("sidecar did not listen: {err}"),
+        }
+    };
+
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    writeln!(
+        writer,
+        "{}",
+        json!({ "jsonrpc": "2.0", "id": "a", "method": "curves" })
+    )
+    .unwrap();
+    let mut response = String::new();
+    reader.read_line(&mut response).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+
+    // a socket that is still served is not replaced
+    let second = Command::new(BIN)
+        .arg("--socket")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(response["id"], "a");
+    assert!(!response["result"].as_array().unwrap().is_empty());
+    assert!(!second.status.success());
+    assert!(String::from_utf8(second.stderr)
+        .unwrap()
+        .contains("already served"));
+}
+
+#[cfg(unix)]
+#[test]
+fn does_not_replace_other_files_with_the_socket() {
+    let path = std::env::temp_dir().join(format!(
+        "stealth-address-sidecar-{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, "not a socket").unwrap();
+    let output = Command::new(BIN)
+        .arg("--socket")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("not a socket"));
+    assert_eq!(contents, "not a socket");
+}
@@ -0,0 +1,3 @@
+fn main() {
+    uniffi::generate_scaffolding("src/stealth_address_kit.udl").unwrap();
+}
@@ -0,0 +1,243 @@
+// the generated scaffolding leaves blank lines after its doc comments
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use std::fmt::{Display, Formatter};
+use stealth_address_kit::{find_curve, DynStealthCurve, InvalidEncoding};
+
+uniffi::include_scaffolding!("stealth_address_kit");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Curve {
+    BabyJubJub,
+    Bandersnatch,
+    Bls12377,
+    Bls12377G2,
+    Bls12381,
+    Bls12381G2,
+    Bn254,
+    Bn254G2,
+    Bw6761,
+    Bw6761G2,
+    Ed25519,
+    EdOnBls12377,
+    Grumpkin,
+    Jubjub,
+    Pallas,
+    Ristretto255,
+    Secp256k1,
+    Secp256r1,
+    Secq256k1,
+    StarkCurve,
+    Vesta,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StealthAddressError {
+    UnsupportedCurve,
+    InvalidEncoding,
+}
+
+impl Display for StealthAddressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StealthAddressError::UnsupportedCurve => {
+                write!(f, "the curve is not enabled in this build")
+            }
+            StealthAddressError::InvalidEncoding => {
+                write!(f, "not a canonical encoding of a key or point")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StealthAddressError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPair {
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetaAddress {
+    pub spending_public_key: Vec<u8>,
+    pub viewing_public_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub stealth_address: Vec<u8>,
+    pub ephemeral_public_key: Vec<u8>,
+    pub view_tag: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanResult {
+    pub index: u64,
+    pub announcement: Announcement,
+    pub stealth_private_key: Vec<u8>,
+}
+
+impl From<InvalidEncoding> for StealthAddressError {
+    fn from(_: InvalidEncoding) -> Self {
+        StealthAddressError::InvalidEncoding
+    }
+}
+
+impl Curve {
+    /// The name of the curve in the sdk's curve registry.
+    fn name(self) -> &'static str {
+        match self {
+            Curve::BabyJubJub => "baby_jub_jub",
+            Curve::Bandersnatch => "bandersnatch",
+            Curve::Bls12377 => "bls12_377",
+            Curve::Bls12377G2 => "bls12_377_g2",
+            Curve::Bls12381 => "bls12_381",
+            Curve::Bls12381G2 => "bls12_381_g2",
+            Curve::Bn254 => "bn254",
+            Curve::Bn254G2 => "bn254_g2",
+            Curve::Bw6761 => "bw6_761",
+            Curve::Bw6761G2 => "bw6_761_g2",
+            Curve::Ed25519 => "ed25519",
+            Curve::EdOnBls12377 => "ed_on_bls12_377",
+            Curve::Grumpkin => "grumpkin",
+            Curve::Jubjub => "jubjub",
+            Curve::Pallas => "pallas",
+            Curve::Ristretto255 => "ristretto255",
+            Curve::Secp256k1 => "secp256k1",
+            Curve::Secp256r1 => "secp256r1",
+            Curve::Secq256k1 => "secq256k1",
+            Curve::StarkCurve => "stark_curve",
+            Curve::Vesta => "vesta",
+        }
+    }
+}
+
+const ALL_CURVES: [Curve; 21] = [
+    Curve::BabyJubJub,
+    Curve::Bandersnatch,
+    Curve::Bls12377,
+    Curve::Bls12377G2,
+    Curve::Bls12381,
+    Curve::Bls12381G2,
+    Curve::Bn254,
+    Curve::Bn254G2,
+    Curve::Bw6761,
+    Curve::Bw6761G2,
+    Curve::Ed25519,
+    Curve::EdOnBls12377,
+    Curve::Grumpkin,
+    Curve::Jubjub,
+    Curve::Pallas,
+    Curve::Ristretto255,
+    Curve::Secp256k1,
+    Curve::Secp256r1,
+    Curve::Secq256k1,
+    Curve::StarkCurve,
+    Curve::Vesta,
+];
+
+fn curve_ops(curve: Curve) -> Option<&'static dyn DynStealthCurve> {
+    find_curve(curve.name())
+}
+
+pub fn supported_curves() -> Vec<Curve> {
+    ALL_CURVES
+        .into_iter()
+        .filter(|curve| curve_ops(*curve).is_some())
+        .collect()
+}
+
+pub struct StealthCurve {
+    curve: Curve,
+    ops: &'static dyn DynStealthCurve,
+}
+
+impl StealthCurve {
+    pub fn new(curve: Curve) -> Result<Self, StealthAddressError> {
+        let ops = curve_ops(curve).ok_or(StealthAddressError::UnsupportedCurve)?;
+        Ok(StealthCurve { curve, ops })
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    pub fn generate_random_fr(&self) -> Vec<u8> {
+        self.ops.generate_random_fr()
+    }
+
+    pub fn random_keypair(&self) -> KeyPair {
+        let (private_key, public_key) = self.ops.random_keypair();
+        KeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    pub fn derive_public_key(&self, private_key: Vec<u8>) -> Result<Vec<u8>, StealthAddressError> {
+        Ok(self.ops.derive_public_key(&private_key)?)
+    }
+
+    pub fn generate_stealth_address(
+        &self,
+        meta_address: MetaAddress,
+        ephemeral_private_key: Vec<u8>,
+    ) -> Result<Announcement, StealthAddressError> {
+        let (stealth_address, view_tag) = self.ops.generate_stealth_address(
+            &meta_address.viewing_public_key,
+            &meta_address.spending_public_key,
+            &ephemeral_private_key,
+        )?;
+        Ok(Announcement {
+            stealth_address,
+            ephemeral_public_key: self.ops.derive_public_key(&ephemeral_private_key)?,
+            view_tag,
+        })
+    }
+
+    pub fn generate_stealth_private_key(
+        &self,
+        announcement: Announcement,
+        viewing_key: Vec<u8>,
+        spending_key: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, StealthAddressError> {
+        Ok(self.ops.generate_stealth_private_key(
+            &announcement.ephemeral_public_key,
+            &viewing_key,
+            &spending_key,
+            announcement.view_tag,
+        )?)
+    }
+
+    pub fn scan(
+        &self,
+        announcements: Vec<Announcement>,
+        viewing_key: Vec<u8>,
+        spending_key: Vec<u8>,
+    ) -> Result<Vec<ScanResult>, StealthAddressError> {
+        let found = self.ops.scan(
+            &announcements
+                .iter()
+                .map(|announcement| {
+                    (
+                        announcement.stealth_address.clone(),
+                        announcement.ephemeral_public_key.clone(),
+                        announcement.view_tag,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            &viewing_key,
+            &spending_key,
+        )?;
+
+        Ok(found
+            .into_iter()
+            .map(|(index, stealth_private_key)| ScanResult {
+                index: index as u64,
+                announcement: announcements[index].clone(),
+                stealth_private_key,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,67 @@
+from stealth_address_kit import *
+
+curves = supported_curves()
+assert len(curves) > 0
+
+for curve_name in curves:
+    curve = StealthCurve(curve_name)
+    assert curve.curve() == curve_name
+
+    # keypairs
+    keypair = curve.random_keypair()
+    assert curve.derive_public_key(keypair.private_key) == keypair.public_key
+    assert curve.generate_random_fr() != curve.generate_random_fr()
+
+    # sending to a meta-address
+    spending = curve.random_keypair()
+    viewing = curve.random_keypair()
+    meta_address = MetaAddress(
+        spending_public_key=spending.public_key,
+        viewing_public_key=viewing.public_key,
+    )
+    ephemeral = curve.random_keypair()
+    announcement = curve.generate_stealth_address(meta_address, ephemeral.private_key)
+    assert announcement.ephemeral_public_key == ephemeral.public_key
+
+    stealth_private_key = curve.generate_stealth_private_key(
+        announcement, viewing.private_key, spending.private_key
+    )
+    assert stealth_private_key is not None
+    assert curve.derive_public_key(stealth_private_key) == announcement.stealth_address
+
+    mismatched = Announcement(
+        stealth_address=announcement.stealth_address,
+        ephemeral_public_key=announcement.ephemeral_public_key,
+        view_tag=(announcement.view_tag + 1) % 2**64,
+    )
+    assert (
+        curve.generate_stealth_private_key(
+            mismatched, viewing.private_key, spending.private_key
+        )
+        is None
+    )
+
+    # scanning only returns the announcements addressed to the recipient
+    other = curve.random_keypair()
+    other_meta_address = MetaAddress(
+        spending_public_key=spending.public_key,
+        viewing_public_key=other.public_key,
+    )
+    announcements = [
+        curve.generate_stealth_address(
+            other_meta_address, curve.random_keypair().private_key
+        ),
+        announcement,
+    ]
+    results = curve.scan(announcements, viewing.private_key, spending.private_key)
+    assert len(results) == 1
+    assert results[0].index == 1
+    assert results[0].announcement == announcement
+    assert results[0].stealth_private_key == stealth_private_key
+
+    # malformed input
+    try:
+        curve.derive_public_key(keypair.private_key + b"\x00")
+        raise AssertionError("expected InvalidEncoding")
+    except StealthAddressError.InvalidEncoding:
+        pass